@@ -10,6 +10,9 @@ fn main() {
             log::info!("Hello world!");
         });
 
+        #[cfg(feature = "unstable")]
+        handle.await.unwrap();
+        #[cfg(not(feature = "unstable"))]
         handle.await;
     })
 }
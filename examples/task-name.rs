@@ -8,10 +8,15 @@ async fn print_name() {
 
 fn main() {
     task::block_on(async {
-        task::Builder::new()
+        let handle = task::Builder::new()
             .name("my-task".to_string())
             .spawn(print_name())
-            .unwrap()
-            .await;
+            .unwrap();
+
+        // With the `unstable` feature, awaiting the handle returns an error if the task panicked.
+        #[cfg(feature = "unstable")]
+        handle.await.unwrap();
+        #[cfg(not(feature = "unstable"))]
+        handle.await;
     })
 }
@@ -38,5 +38,6 @@ pub async fn canonicalize<P: AsRef<Path>>(path: P) -> io::Result<PathBuf> {
             .map(Into::into)
             .context(|| format!("could not canonicalize `{}`", path.display()))
     })
+    .output()
     .await
 }
@@ -46,5 +46,6 @@ pub async fn copy<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<
         std::fs::copy(&from, &to)
             .context(|| format!("could not copy `{}` to `{}`", from.display(), to.display()))
     })
+    .output()
     .await
 }
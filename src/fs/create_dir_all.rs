@@ -34,5 +34,6 @@ pub async fn create_dir_all<P: AsRef<Path>>(path: P) -> io::Result<()> {
         std::fs::create_dir_all(&path)
             .context(|| format!("could not create directory path `{}`", path.display()))
     })
+    .output()
     .await
 }
@@ -116,6 +116,7 @@ impl File {
         let file = spawn_blocking(move || {
             std::fs::File::open(&path).context(|| format!("could not open `{}`", path.display()))
         })
+        .output()
         .await?;
         Ok(File::new(file, true))
     }
@@ -155,6 +156,7 @@ impl File {
             std::fs::File::create(&path)
                 .context(|| format!("could not create `{}`", path.display()))
         })
+        .output()
         .await?;
         Ok(File::new(file, true))
     }
@@ -188,7 +190,7 @@ impl File {
         })
         .await?;
 
-        spawn_blocking(move || state.file.sync_all()).await
+        spawn_blocking(move || state.file.sync_all()).output().await
     }
 
     /// Synchronizes OS-internal buffered contents to disk.
@@ -224,7 +226,9 @@ impl File {
         })
         .await?;
 
-        spawn_blocking(move || state.file.sync_data()).await
+        spawn_blocking(move || state.file.sync_data())
+            .output()
+            .await
     }
 
     /// Truncates or extends the file.
@@ -257,7 +261,9 @@ impl File {
         })
         .await?;
 
-        spawn_blocking(move || state.file.set_len(size)).await
+        spawn_blocking(move || state.file.set_len(size))
+            .output()
+            .await
     }
 
     /// Reads the file's metadata.
@@ -276,7 +282,7 @@ impl File {
     /// ```
     pub async fn metadata(&self) -> io::Result<Metadata> {
         let file = self.file.clone();
-        spawn_blocking(move || file.metadata()).await
+        spawn_blocking(move || file.metadata()).output().await
     }
 
     /// Changes the permissions on the file.
@@ -305,7 +311,9 @@ impl File {
     /// ```
     pub async fn set_permissions(&self, perm: Permissions) -> io::Result<()> {
         let file = self.file.clone();
-        spawn_blocking(move || file.set_permissions(perm)).await
+        spawn_blocking(move || file.set_permissions(perm))
+            .output()
+            .await
     }
 }
 
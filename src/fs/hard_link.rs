@@ -42,5 +42,6 @@ pub async fn hard_link<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Re
             )
         })
     })
+    .output()
     .await
 }
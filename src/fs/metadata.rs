@@ -34,7 +34,9 @@ use crate::task::spawn_blocking;
 /// ```
 pub async fn metadata<P: AsRef<Path>>(path: P) -> io::Result<Metadata> {
     let path = path.as_ref().to_owned();
-    spawn_blocking(move || std::fs::metadata(path)).await
+    spawn_blocking(move || std::fs::metadata(path))
+        .output()
+        .await
 }
 
 cfg_not_docs! {
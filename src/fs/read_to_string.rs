@@ -42,5 +42,6 @@ pub async fn read_to_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
         std::fs::read_to_string(&path)
             .context(|| format!("could not read file `{}`", path.display()))
     })
+    .output()
     .await
 }
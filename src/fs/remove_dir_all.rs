@@ -34,5 +34,6 @@ pub async fn remove_dir_all<P: AsRef<Path>>(path: P) -> io::Result<()> {
         std::fs::remove_dir_all(&path)
             .context(|| format!("could not remove directory `{}`", path.display()))
     })
+    .output()
     .await
 }
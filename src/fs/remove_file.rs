@@ -34,5 +34,6 @@ pub async fn remove_file<P: AsRef<Path>>(path: P) -> io::Result<()> {
         std::fs::remove_file(&path)
             .context(|| format!("could not remove file `{}`", path.display()))
     })
+    .output()
     .await
 }
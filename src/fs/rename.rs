@@ -44,5 +44,6 @@ pub async fn rename<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Resul
             )
         })
     })
+    .output()
     .await
 }
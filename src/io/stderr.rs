@@ -1,6 +1,5 @@
 use std::pin::Pin;
 use std::sync::Mutex;
-use std::future::Future;
 
 use crate::io::{self, Write};
 use crate::task::{spawn_blocking, Context, JoinHandle, Poll};
@@ -133,7 +132,9 @@ impl Stderr {
     pub async fn lock(&self) -> StderrLock<'static> {
         static STDERR: Lazy<std::io::Stderr> = Lazy::new(std::io::stderr);
 
-        spawn_blocking(move || StderrLock(STDERR.lock())).await
+        spawn_blocking(move || StderrLock(STDERR.lock()))
+            .output()
+            .await
     }
 }
 
@@ -182,7 +183,7 @@ impl Write for Stderr {
                     }
                 }
                 // Poll the asynchronous operation the stderr is currently blocked on.
-                State::Busy(task) => *state = futures_core::ready!(Pin::new(task).poll(cx)),
+                State::Busy(task) => *state = futures_core::ready!(task.poll_output(cx)),
             }
         }
     }
@@ -210,7 +211,7 @@ impl Write for Stderr {
                     }
                 }
                 // Poll the asynchronous operation the stderr is currently blocked on.
-                State::Busy(task) => *state = futures_core::ready!(Pin::new(task).poll(cx)),
+                State::Busy(task) => *state = futures_core::ready!(task.poll_output(cx)),
             }
         }
     }
@@ -1,4 +1,3 @@
-use std::future::Future;
 use std::pin::Pin;
 use std::sync::Mutex;
 
@@ -158,7 +157,7 @@ impl Stdin {
                         }
                     }
                     // Poll the asynchronous operation the stdin is currently blocked on.
-                    State::Busy(task) => *state = futures_core::ready!(Pin::new(task).poll(cx)),
+                    State::Busy(task) => *state = futures_core::ready!(task.poll_output(cx)),
                 }
             }
         })
@@ -192,7 +191,9 @@ impl Stdin {
     pub async fn lock(&self) -> StdinLock<'static> {
         static STDIN: Lazy<std::io::Stdin> = Lazy::new(std::io::stdin);
 
-        spawn_blocking(move || StdinLock(STDIN.lock())).await
+        spawn_blocking(move || StdinLock(STDIN.lock()))
+            .output()
+            .await
     }
 }
 
@@ -240,7 +241,7 @@ impl Read for Stdin {
                     }
                 }
                 // Poll the asynchronous operation the stdin is currently blocked on.
-                State::Busy(task) => *state = futures_core::ready!(Pin::new(task).poll(cx)),
+                State::Busy(task) => *state = futures_core::ready!(task.poll_output(cx)),
             }
         }
     }
@@ -1,6 +1,5 @@
 use std::pin::Pin;
 use std::sync::Mutex;
-use std::future::Future;
 
 use crate::io::{self, Write};
 use crate::task::{spawn_blocking, Context, JoinHandle, Poll};
@@ -133,7 +132,9 @@ impl Stdout {
     pub async fn lock(&self) -> StdoutLock<'static> {
         static STDOUT: Lazy<std::io::Stdout> = Lazy::new(std::io::stdout);
 
-        spawn_blocking(move || StdoutLock(STDOUT.lock())).await
+        spawn_blocking(move || StdoutLock(STDOUT.lock()))
+            .output()
+            .await
     }
 }
 
@@ -182,7 +183,7 @@ impl Write for Stdout {
                     }
                 }
                 // Poll the asynchronous operation the stdout is currently blocked on.
-                State::Busy(task) => *state = futures_core::ready!(Pin::new(task).poll(cx)),
+                State::Busy(task) => *state = futures_core::ready!(task.poll_output(cx)),
             }
         }
     }
@@ -210,7 +211,7 @@ impl Write for Stdout {
                     }
                 }
                 // Poll the asynchronous operation the stdout is currently blocked on.
-                State::Busy(task) => *state = futures_core::ready!(Pin::new(task).poll(cx)),
+                State::Busy(task) => *state = futures_core::ready!(task.poll_output(cx)),
             }
         }
     }
@@ -214,6 +214,21 @@
 //! features = ["attributes"]
 //! ```
 //!
+//! Items marked with
+//! <span
+//!   class="module-item stab portability"
+//!   style="display: inline; border-radius: 3px; padding: 2px; font-size: 80%; line-height: 1.2;"
+//! ><code>backtrace</code></span>
+//! are available only when the `backtrace` Cargo feature is enabled along with `unstable`. It
+//! makes every task capture a backtrace of the code that spawned it, which is slow and only meant
+//! for debugging:
+//!
+//! ```toml
+//! [dependencies.async-std]
+//! version = "1.6.0"
+//! features = ["unstable", "backtrace"]
+//! ```
+//!
 //! Additionally it's possible to only use the core traits and combinators by
 //! only enabling the `std` Cargo feature:
 //!
@@ -248,6 +263,12 @@
 //! threads report to the operating system. The default value is
 //! `"async-std/runtime"`.
 //!
+//! With the `unstable` feature enabled, the runtime can also be configured
+//! programmatically using [`rt::Builder`], which takes precedence over
+//! these environment variables.
+//!
+//! [`rt::Builder`]: rt/struct.Builder.html
+//!
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(feature = "docs", feature(doc_cfg))]
@@ -289,7 +310,10 @@ cfg_default! {
     pub mod fs;
     pub mod path;
     pub mod net;
-    #[cfg(not(target_os = "unknown"))]
+    #[cfg(all(not(target_os = "unknown"), feature = "unstable"))]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub mod rt;
+    #[cfg(all(not(target_os = "unknown"), not(feature = "unstable")))]
     pub(crate) mod rt;
 }
 
@@ -174,6 +174,17 @@ macro_rules! eprintln {
 /// Each declared value is of the accessor type [`LocalKey`].
 ///
 /// [`LocalKey`]: task/struct.LocalKey.html
+#[cfg_attr(
+    feature = "unstable",
+    doc = r#"
+A declaration can start with the `#[inherit]` attribute to make the value inheritable. When a
+task is spawned, including with [`spawn_blocking`], it starts with a clone of the spawning task's
+value instead of a freshly initialized one. The type of an inheritable value must implement
+`Clone`. This requires the `unstable` feature.
+
+[`spawn_blocking`]: task/fn.spawn_blocking.html
+"#
+)]
 ///
 /// # Examples
 ///
@@ -193,11 +204,46 @@ macro_rules! eprintln {
 ///     assert_eq!(v, 5);
 /// });
 /// ```
+#[cfg_attr(
+    feature = "unstable",
+    doc = r#"
+Inheriting a value:
+
+```
+#
+use std::cell::Cell;
+
+use async_std::prelude::*;
+use async_std::task;
+
+task_local! {
+    #[inherit]
+    static REQUEST_ID: Cell<u64> = Cell::new(0);
+}
+
+task::block_on(async {
+    REQUEST_ID.with(|id| id.set(42));
+
+    let child = task::spawn(async { REQUEST_ID.with(|id| id.get()) });
+    assert_eq!(child.await.unwrap(), 42);
+});
+```
+"#
+)]
 #[cfg(feature = "default")]
 #[macro_export]
 macro_rules! task_local {
     () => ();
 
+    (#[inherit] $(#[$attr:meta])* $vis:vis static $name:ident: $t:ty = $init:expr) => (
+        $crate::__task_local_inherit!($(#[$attr])* $vis static $name: $t = $init);
+    );
+
+    (#[inherit] $(#[$attr:meta])* $vis:vis static $name:ident: $t:ty = $init:expr; $($rest:tt)*) => (
+        $crate::task_local!(#[inherit] $(#[$attr])* $vis static $name: $t = $init);
+        $crate::task_local!($($rest)*);
+    );
+
     ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty = $init:expr) => (
         $(#[$attr])* $vis static $name: $crate::task::LocalKey<$t> = {
             #[inline]
@@ -207,6 +253,7 @@ macro_rules! task_local {
 
             $crate::task::LocalKey {
                 __init,
+                __inherit: ::std::option::Option::None,
                 __key: ::std::sync::atomic::AtomicU32::new(0),
             }
         };
@@ -217,3 +264,38 @@ macro_rules! task_local {
         $crate::task_local!($($rest)*);
     );
 }
+
+/// Declares an inheritable task-local value, for the `#[inherit]` form of `task_local!`.
+#[cfg(all(feature = "default", feature = "unstable"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __task_local_inherit {
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty = $init:expr) => (
+        $(#[$attr])* $vis static $name: $crate::task::LocalKey<$t> = {
+            #[inline]
+            fn __init() -> $t {
+                $init
+            }
+
+            fn __inherit(value: &$t) -> $t {
+                ::std::clone::Clone::clone(value)
+            }
+
+            $crate::task::LocalKey {
+                __init,
+                __inherit: ::std::option::Option::Some(__inherit),
+                __key: ::std::sync::atomic::AtomicU32::new(0),
+            }
+        };
+    );
+}
+
+/// Rejects the `#[inherit]` form of `task_local!` without the `unstable` feature.
+#[cfg(all(feature = "default", not(feature = "unstable")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __task_local_inherit {
+    ($($tt:tt)*) => (
+        compile_error!("inheritable task-locals require the `unstable` feature of async-std");
+    );
+}
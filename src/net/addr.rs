@@ -89,7 +89,7 @@ impl<I: Iterator<Item = SocketAddr>> Future for ToSocketAddrsFuture<I> {
 
         match state {
             ToSocketAddrsFuture::Resolving(mut task) => {
-                let poll = Pin::new(&mut task).poll(cx);
+                let poll = task.poll_output(cx);
                 if poll.is_pending() {
                     *this = ToSocketAddrsFuture::Resolving(task);
                 }
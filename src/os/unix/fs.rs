@@ -26,7 +26,9 @@ use crate::task::spawn_blocking;
 pub async fn symlink<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> io::Result<()> {
     let src = src.as_ref().to_owned();
     let dst = dst.as_ref().to_owned();
-    spawn_blocking(move || std::os::unix::fs::symlink(&src, &dst)).await
+    spawn_blocking(move || std::os::unix::fs::symlink(&src, &dst))
+        .output()
+        .await
 }
 
 cfg_not_docs! {
@@ -26,7 +26,9 @@ use crate::task::spawn_blocking;
 pub async fn symlink_dir<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> io::Result<()> {
     let src = src.as_ref().to_owned();
     let dst = dst.as_ref().to_owned();
-    spawn_blocking(move || std::os::windows::fs::symlink_dir(&src, &dst)).await
+    spawn_blocking(move || std::os::windows::fs::symlink_dir(&src, &dst))
+        .output()
+        .await
 }
 
 /// Creates a new file symbolic link on the filesystem.
@@ -51,5 +53,7 @@ pub async fn symlink_dir<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> io::
 pub async fn symlink_file<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> io::Result<()> {
     let src = src.as_ref().to_owned();
     let dst = dst.as_ref().to_owned();
-    spawn_blocking(move || std::os::windows::fs::symlink_file(&src, &dst)).await
+    spawn_blocking(move || std::os::windows::fs::symlink_file(&src, &dst))
+        .output()
+        .await
 }
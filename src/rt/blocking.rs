@@ -0,0 +1,215 @@
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// A blocking task that is ready to be run.
+type Runnable = async_task::Task<()>;
+
+/// A pool of threads running blocking tasks.
+///
+/// Threads are started on demand, up to a limit, and exit after staying idle for a while, or as
+/// soon as they are idle once the pool has been shut down.
+pub(crate) struct Pool {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    /// The mutable state of the pool.
+    state: Mutex<State>,
+
+    /// Notified when a task is pushed into the queue.
+    cvar: Condvar,
+
+    /// The maximum number of threads.
+    max_threads: usize,
+
+    /// How long a thread waits for a new task before it exits.
+    keep_alive: Duration,
+
+    /// The name of the pool's threads.
+    thread_name: String,
+
+    /// The stack size of the pool's threads, if not the platform's default.
+    stack_size: Option<usize>,
+}
+
+struct State {
+    /// Tasks waiting for a thread.
+    queue: VecDeque<Runnable>,
+
+    /// The number of tasks being run.
+    running: usize,
+
+    /// The number of threads.
+    threads: usize,
+
+    /// The number of threads waiting for a task.
+    idle: usize,
+
+    /// Set when the pool is shut down.
+    stopped: bool,
+}
+
+impl Pool {
+    /// Creates an empty pool.
+    pub(crate) fn new(
+        max_threads: usize,
+        keep_alive: Duration,
+        thread_name: String,
+        stack_size: Option<usize>,
+    ) -> Pool {
+        Pool {
+            inner: Arc::new(Inner {
+                state: Mutex::new(State {
+                    queue: VecDeque::new(),
+                    running: 0,
+                    threads: 0,
+                    idle: 0,
+                    stopped: false,
+                }),
+                cvar: Condvar::new(),
+                max_threads: max_threads.max(1),
+                keep_alive,
+                thread_name,
+                stack_size,
+            }),
+        }
+    }
+
+    /// Spawns a blocking task onto the pool.
+    pub(crate) fn spawn<F, T>(&self, f: F) -> async_task::JoinHandle<T, ()>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let inner = self.inner.clone();
+        let schedule = move |runnable| inner.schedule(runnable);
+        let (runnable, handle) = async_task::spawn(async move { f() }, schedule, ());
+
+        runnable.schedule();
+        handle
+    }
+
+    /// Returns the number of blocking tasks that have not completed yet.
+    pub(crate) fn live_tasks(&self) -> usize {
+        let state = self.inner.state.lock().unwrap();
+        state.queue.len() + state.running
+    }
+
+    /// Returns the number of threads in the pool.
+    pub(crate) fn thread_count(&self) -> usize {
+        self.inner.state.lock().unwrap().threads
+    }
+
+    /// Shuts the pool down.
+    ///
+    /// Tasks that have been spawned still run to completion, and new ones can still be spawned,
+    /// but threads exit as soon as there are no tasks left for them instead of staying idle.
+    pub(crate) fn shutdown(&self) {
+        self.inner.state.lock().unwrap().stopped = true;
+        self.inner.cvar.notify_all();
+    }
+}
+
+impl Inner {
+    /// Pushes a task into the queue, starting a new thread if none is available to run it.
+    ///
+    /// If no thread can be started and the pool has none left to run the task, the task is
+    /// dropped, so that its `JoinHandle` resolves as cancelled instead of waiting forever.
+    fn schedule(self: &Arc<Self>, runnable: Runnable) {
+        let mut state = self.state.lock().unwrap();
+        state.queue.push_back(runnable);
+
+        if state.queue.len() > state.idle && state.threads < self.max_threads {
+            state.threads += 1;
+
+            let inner = self.clone();
+            let mut builder = thread::Builder::new().name(self.thread_name.clone());
+            if let Some(size) = self.stack_size {
+                builder = builder.stack_size(size);
+            }
+            let spawned = builder.spawn(move || inner.main_loop());
+
+            if let Err(err) = spawned {
+                state.threads -= 1;
+                kv_log_macro::error!("cannot start a thread driving blocking tasks", {
+                    error: &*err.to_string(),
+                    threads: state.threads,
+                });
+
+                // Otherwise, the task stays in the queue, to be run by one of the other threads.
+                if state.threads == 0 {
+                    let runnable = state.queue.pop_back();
+                    drop(state);
+                    drop(runnable);
+                    return;
+                }
+            }
+        }
+
+        self.cvar.notify_one();
+    }
+
+    /// Runs tasks until the thread stays idle for longer than the keep-alive timeout, or until it
+    /// runs out of tasks after the pool has been shut down.
+    fn main_loop(&self) {
+        let mut state = self.state.lock().unwrap();
+
+        loop {
+            if let Some(runnable) = state.queue.pop_front() {
+                state.running += 1;
+                drop(state);
+
+                // A panic is propagated to the task's `JoinHandle`, so it shouldn't stop the thread.
+                let _ = panic::catch_unwind(AssertUnwindSafe(|| runnable.run()));
+
+                state = self.state.lock().unwrap();
+                state.running -= 1;
+                continue;
+            }
+
+            if state.stopped {
+                state.threads -= 1;
+                return;
+            }
+
+            state.idle += 1;
+            let (s, res) = self.cvar.wait_timeout(state, self.keep_alive).unwrap();
+            state = s;
+            state.idle -= 1;
+
+            if res.timed_out() && state.queue.is_empty() {
+                state.threads -= 1;
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Pool;
+    use crate::task;
+
+    #[test]
+    fn cancel_when_no_thread_starts() {
+        // A stack this large cannot be allocated, so no thread of the pool ever starts.
+        let pool = Pool::new(
+            1,
+            Duration::from_secs(1),
+            "blocking".to_string(),
+            Some(1 << 50),
+        );
+
+        for _ in 0..2 {
+            let handle = pool.spawn(|| 1 + 2);
+            assert_eq!(task::block_on(handle), None);
+            assert_eq!(pool.live_tasks(), 0);
+            assert_eq!(pool.thread_count(), 0);
+        }
+    }
+}
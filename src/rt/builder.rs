@@ -0,0 +1,312 @@
+use std::env;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::io;
+use crate::rt::{self, Executor, PanicPolicy, Pool, Runtime, RUNTIME};
+
+/// A callback invoked on runtime threads.
+type Callback = Arc<dyn Fn() + Send + Sync>;
+
+/// Runtime builder that configures the global runtime before it is started.
+///
+/// Settings that are not configured on the builder fall back to the `ASYNC_STD_THREAD_COUNT` and
+/// `ASYNC_STD_THREAD_NAME` environment variables, and then to the built-in defaults.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "unstable")]
+/// # {
+/// use async_std::rt;
+///
+/// rt::Builder::new()
+///     .thread_count(4)
+///     .stack_size(4 * 1024 * 1024)
+///     .on_thread_start(|| println!("runtime thread started"))
+///     .init()
+///     .expect("the runtime has already been started");
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct Builder {
+    thread_count: Option<usize>,
+    thread_name: Option<String>,
+    stack_size: Option<usize>,
+    on_thread_start: Option<Callback>,
+    on_thread_stop: Option<Callback>,
+    slow_poll_threshold: Option<Duration>,
+    max_blocking_threads: Option<usize>,
+    blocking_keep_alive: Option<Duration>,
+    blocking_thread_name: Option<String>,
+    panic_policy: Option<PanicPolicy>,
+}
+
+impl Builder {
+    /// Creates a new builder.
+    #[inline]
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    /// Configures the number of threads the runtime will start.
+    ///
+    /// By default, this is one per logical cpu. A value of zero is treated as one.
+    pub fn thread_count(mut self, count: usize) -> Builder {
+        self.thread_count = Some(count);
+        self
+    }
+
+    /// Configures the name that runtime threads report to the operating system.
+    ///
+    /// The default name is `"async-std/runtime"`.
+    pub fn thread_name(mut self, name: String) -> Builder {
+        self.thread_name = Some(name);
+        self
+    }
+
+    /// Configures the stack size, in bytes, of runtime threads, including the threads of the
+    /// blocking pool.
+    ///
+    /// By default, the platform's default stack size for spawned threads is used.
+    pub fn stack_size(mut self, size: usize) -> Builder {
+        self.stack_size = Some(size);
+        self
+    }
+
+    /// Registers a callback that is invoked on every runtime thread when it starts.
+    pub fn on_thread_start<F>(mut self, f: F) -> Builder
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_thread_start = Some(Arc::new(f));
+        self
+    }
+
+    /// Registers a callback that is invoked on every runtime thread right before it stops.
+    pub fn on_thread_stop<F>(mut self, f: F) -> Builder
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_thread_stop = Some(Arc::new(f));
+        self
+    }
+
+    /// Enables warnings about tasks that take longer than `threshold` to be polled.
+    ///
+    /// A task that blocks inside `poll`, for example by calling `std::thread::sleep` or doing
+    /// synchronous I/O, stalls the runtime thread it runs on. With this setting, every poll of a
+    /// task running on the runtime is timed, and polls that exceed `threshold` are reported as
+    /// warnings through the [`log`] crate, along with the id and name of the task.
+    ///
+    /// By default, polls are not timed.
+    ///
+    /// [`log`]: https://docs.rs/log
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "unstable")]
+    /// # {
+    /// use std::time::Duration;
+    ///
+    /// use async_std::rt;
+    ///
+    /// let runtime = rt::Builder::new()
+    ///     .slow_poll_threshold(Duration::from_millis(10))
+    ///     .build()
+    ///     .expect("cannot start the runtime");
+    ///
+    /// // This logs a warning because the task blocks its thread.
+    /// let handle = runtime.spawn(async {
+    ///     std::thread::sleep(Duration::from_millis(20));
+    /// });
+    /// runtime.block_on(handle).unwrap();
+    /// # }
+    /// ```
+    pub fn slow_poll_threshold(mut self, threshold: Duration) -> Builder {
+        self.slow_poll_threshold = Some(threshold);
+        self
+    }
+
+    /// Configures the maximum number of threads in the blocking pool.
+    ///
+    /// The blocking pool runs the tasks spawned with [`task::spawn_blocking`], which includes
+    /// most file system operations. Its threads are started on demand, and when all of them are
+    /// busy, new blocking tasks wait for one to become available.
+    ///
+    /// The default limit is 500. A value of zero is treated as one.
+    ///
+    /// [`task::spawn_blocking`]: ../task/fn.spawn_blocking.html
+    pub fn max_blocking_threads(mut self, count: usize) -> Builder {
+        self.max_blocking_threads = Some(count);
+        self
+    }
+
+    /// Configures how long an idle thread of the blocking pool waits for a new task before
+    /// exiting.
+    ///
+    /// The default is 500 milliseconds.
+    pub fn blocking_keep_alive(mut self, timeout: Duration) -> Builder {
+        self.blocking_keep_alive = Some(timeout);
+        self
+    }
+
+    /// Configures the name that threads of the blocking pool report to the operating system.
+    ///
+    /// The default name is `"async-std/blocking"`.
+    pub fn blocking_thread_name(mut self, name: String) -> Builder {
+        self.blocking_thread_name = Some(name);
+        self
+    }
+
+    /// Configures what the runtime does when a task panics.
+    ///
+    /// By default, the panic is logged and the runtime keeps running other tasks. See
+    /// [`PanicPolicy`] for the available policies.
+    ///
+    /// [`PanicPolicy`]: enum.PanicPolicy.html
+    pub fn panic_policy(mut self, policy: PanicPolicy) -> Builder {
+        self.panic_policy = Some(policy);
+        self
+    }
+
+    /// Starts the global runtime with the configured settings.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error of kind [`AlreadyExists`] if the global runtime has already
+    /// been started, either by an earlier call to this method or because a task has already been
+    /// spawned. It also returns an error if a runtime thread could not be started.
+    ///
+    /// [`AlreadyExists`]: ../io/enum.ErrorKind.html#variant.AlreadyExists
+    pub fn init(self) -> io::Result<()> {
+        let mut started = false;
+        RUNTIME.get_or_try_init(|| {
+            started = true;
+            self.build()
+        })?;
+
+        if started {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "the runtime has already been started",
+            ))
+        }
+    }
+
+    /// Creates a new runtime with the configured settings.
+    ///
+    /// The new runtime is isolated from the global runtime: it has its own threads, and tasks
+    /// spawned onto it never run on any other runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "unstable")]
+    /// # {
+    /// use async_std::rt;
+    ///
+    /// let runtime = rt::Builder::new()
+    ///     .thread_count(1)
+    ///     .thread_name("batch".to_string())
+    ///     .build()
+    ///     .expect("cannot start the runtime");
+    ///
+    /// let res = runtime.block_on(async { 1 + 2 });
+    /// assert_eq!(res, 3);
+    /// # }
+    /// ```
+    pub fn build(self) -> io::Result<Runtime> {
+        let thread_count = self
+            .thread_count
+            .unwrap_or_else(|| {
+                env::var("ASYNC_STD_THREAD_COUNT")
+                    .map(|env| {
+                        env.parse()
+                            .expect("ASYNC_STD_THREAD_COUNT must be a number")
+                    })
+                    .unwrap_or_else(|_| num_cpus::get())
+            })
+            .max(1);
+
+        let blocking = self.blocking_pool();
+
+        let thread_name = self.thread_name.unwrap_or_else(|| {
+            env::var("ASYNC_STD_THREAD_NAME").unwrap_or_else(|_| "async-std/runtime".to_string())
+        });
+
+        // If a thread fails to start, dropping the runtime stops the threads started so far.
+        let runtime = Runtime {
+            executor: Arc::new(Executor::new(
+                thread_count,
+                self.slow_poll_threshold,
+                blocking,
+                None,
+                self.panic_policy.unwrap_or_default(),
+            )),
+            threads: Mutex::new(Vec::with_capacity(thread_count)),
+        };
+
+        for index in 0..thread_count {
+            let mut builder = thread::Builder::new().name(thread_name.clone());
+            if let Some(size) = self.stack_size {
+                builder = builder.stack_size(size);
+            }
+
+            let executor = runtime.executor.clone();
+            let on_start = self.on_thread_start.clone();
+            let on_stop = self.on_thread_stop.clone();
+
+            let handle = builder.spawn(move || {
+                rt::enter(&executor, || {
+                    if let Some(f) = on_start {
+                        f();
+                    }
+                    defer! {
+                        if let Some(f) = on_stop {
+                            f();
+                        }
+                    }
+
+                    smol::run(executor.worker(index))
+                })
+            })?;
+            runtime.threads.lock().unwrap().push(handle);
+        }
+
+        Ok(runtime)
+    }
+
+    /// Creates a blocking pool with the configured settings.
+    pub(crate) fn blocking_pool(&self) -> Pool {
+        Pool::new(
+            self.max_blocking_threads.unwrap_or(500),
+            self.blocking_keep_alive
+                .unwrap_or_else(|| Duration::from_millis(500)),
+            self.blocking_thread_name
+                .clone()
+                .unwrap_or_else(|| "async-std/blocking".to_string()),
+            self.stack_size,
+        )
+    }
+}
+
+impl fmt::Debug for Builder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builder")
+            .field("thread_count", &self.thread_count)
+            .field("thread_name", &self.thread_name)
+            .field("stack_size", &self.stack_size)
+            .field("slow_poll_threshold", &self.slow_poll_threshold)
+            .field("max_blocking_threads", &self.max_blocking_threads)
+            .field("blocking_keep_alive", &self.blocking_keep_alive)
+            .field("blocking_thread_name", &self.blocking_thread_name)
+            .field("panic_policy", &self.panic_policy)
+            .finish()
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+use crate::rt;
+
+/// A virtual clock that only moves forward when it is advanced.
+pub(crate) struct Clock {
+    /// The mutable state of the clock.
+    state: Mutex<State>,
+
+    /// Generates unique identifiers for timers.
+    next_id: AtomicUsize,
+}
+
+struct State {
+    /// The current virtual time.
+    now: Instant,
+
+    /// Registered timers, ordered by deadline and then by creation.
+    timers: BTreeMap<(Instant, usize), Waker>,
+}
+
+impl Clock {
+    /// Creates a clock starting at the current real time.
+    pub(crate) fn new() -> Clock {
+        Clock {
+            state: Mutex::new(State {
+                now: Instant::now(),
+                timers: BTreeMap::new(),
+            }),
+            next_id: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the current virtual time.
+    pub(crate) fn now(&self) -> Instant {
+        self.state.lock().unwrap().now
+    }
+
+    /// Moves the clock forward and fires the timers that are due.
+    pub(crate) fn advance(&self, dur: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.now += dur;
+        self.fire(state);
+    }
+
+    /// Moves the clock forward to the earliest deadline and fires the timers that are due.
+    ///
+    /// Returns `false` if there are no timers.
+    pub(crate) fn advance_to_next(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let deadline = match state.timers.keys().next() {
+            Some(&(deadline, _)) => deadline,
+            None => return false,
+        };
+
+        if deadline > state.now {
+            state.now = deadline;
+        }
+        self.fire(state);
+        true
+    }
+
+    /// Wakes the timers whose deadline has passed.
+    fn fire(&self, mut state: MutexGuard<'_, State>) {
+        let now = state.now;
+        let pending = state.timers.split_off(&(now, usize::MAX));
+        let due = std::mem::replace(&mut state.timers, pending);
+        drop(state);
+
+        for (_, w) in due {
+            w.wake();
+        }
+    }
+}
+
+/// A timer that completes after a duration.
+///
+/// The duration is measured by the virtual clock of the runtime the timer is created on, or by
+/// the real clock if that runtime doesn't have one.
+pub(crate) struct Timer(Inner);
+
+enum Inner {
+    Real(smol::Timer),
+    Virtual {
+        clock: Arc<Clock>,
+        deadline: Instant,
+        id: usize,
+    },
+}
+
+impl Timer {
+    /// Creates a timer that completes after `dur`.
+    pub(crate) fn after(dur: Duration) -> Timer {
+        match rt::clock() {
+            Some(clock) => Timer(Inner::Virtual {
+                deadline: clock.now() + dur,
+                id: clock.next_id.fetch_add(1, Ordering::Relaxed),
+                clock,
+            }),
+            None => Timer(Inner::Real(smol::Timer::after(dur))),
+        }
+    }
+}
+
+impl Future for Timer {
+    type Output = Instant;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match &mut self.0 {
+            Inner::Real(timer) => Pin::new(timer).poll(cx),
+            Inner::Virtual {
+                clock,
+                deadline,
+                id,
+            } => {
+                let mut state = clock.state.lock().unwrap();
+                if state.now >= *deadline {
+                    state.timers.remove(&(*deadline, *id));
+                    Poll::Ready(*deadline)
+                } else {
+                    state.timers.insert((*deadline, *id), cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        if let Inner::Virtual {
+            clock,
+            deadline,
+            id,
+        } = &self.0
+        {
+            clock.state.lock().unwrap().timers.remove(&(*deadline, *id));
+        }
+    }
+}
+
+impl fmt::Debug for Timer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Timer { .. }")
+    }
+}
@@ -0,0 +1,779 @@
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::ptr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+use slab::Slab;
+
+use crate::io;
+use crate::rt::{Clock, Metrics, PanicPolicy, Pool};
+use crate::task::Priority;
+
+/// A task that is ready to be run.
+type Runnable = async_task::Task<()>;
+
+/// The executor accepts new tasks.
+const RUNNING: usize = 0;
+
+/// The executor no longer accepts new tasks, but keeps running the existing ones.
+const CLOSED: usize = 1;
+
+/// The executor drops the remaining tasks instead of running them.
+const CANCELLING: usize = 2;
+
+/// The executor has stopped and its workers exit.
+const STOPPED: usize = 3;
+
+/// The number of tasks a worker runs before yielding back to the reactor.
+const YIELD_INTERVAL: usize = 64;
+
+/// The number of shards the set of live tasks is split into.
+const SHARDS: usize = 16;
+
+thread_local! {
+    /// The executor and the index of the worker running on the current thread, if any.
+    static WORKER: Cell<Option<(*const Executor, usize)>> = const { Cell::new(None) };
+}
+
+/// An executor running tasks on a fixed set of workers, and blocking tasks on a thread pool.
+///
+/// Like the executor of `smol`, every worker has a local queue that receives the tasks woken up
+/// while it runs, so that workers don't contend on a single queue. Tasks scheduled from other
+/// threads go into a shared queue, and idle workers steal tasks from the local queues of busy
+/// ones.
+pub(crate) struct Executor {
+    /// One of `RUNNING`, `CLOSED`, `CANCELLING` or `STOPPED`.
+    state: AtomicUsize,
+
+    /// Tasks scheduled from outside of the workers.
+    injector: Mutex<Queue>,
+
+    /// The number of tasks in the shared queue and in the local queues, for each priority level.
+    queued: [AtomicUsize; Priority::COUNT],
+
+    /// The state of each worker.
+    workers: Vec<WorkerState>,
+
+    /// The number of workers waiting for tasks.
+    sleeping: AtomicUsize,
+
+    /// Tasks that have been spawned and have not completed yet, split into shards.
+    ///
+    /// Each entry holds a waker that reschedules the task, which is used to cancel it.
+    tasks: Vec<Mutex<Slab<Option<Waker>>>>,
+
+    /// The number of tasks that have been spawned and have not completed yet.
+    live: AtomicUsize,
+
+    /// The lock `idle` is waited on with.
+    idle_lock: Mutex<()>,
+
+    /// Notified when the last live task completes, or when a task gets scheduled while tasks are
+    /// being cancelled.
+    idle: Condvar,
+
+    /// The total number of spawned tasks.
+    spawned: AtomicUsize,
+
+    /// The total number of completed or cancelled tasks.
+    completed: AtomicUsize,
+
+    /// The worker that gets the next pinned task without a chosen worker.
+    next_worker: AtomicUsize,
+
+    /// Polls that take longer than this are reported as warnings.
+    pub(crate) slow_poll_threshold: Option<Duration>,
+
+    /// The pool running blocking tasks.
+    pub(crate) blocking: Pool,
+
+    /// The virtual clock measuring time for timers, if time is virtual.
+    pub(crate) clock: Option<Arc<Clock>>,
+
+    /// What to do when a task panics.
+    pub(crate) panic_policy: PanicPolicy,
+}
+
+/// The state of a worker shared with the executor.
+struct WorkerState {
+    /// The waker of the worker, registered while it waits for tasks.
+    sleeper: Mutex<Option<Waker>>,
+
+    /// Tasks scheduled by the worker, which other workers may steal.
+    local: Mutex<Queue>,
+
+    /// Tasks pinned to the worker that are ready to be run.
+    pinned: Mutex<VecDeque<Runnable>>,
+
+    /// The total time the worker has spent running tasks, in nanoseconds.
+    busy: AtomicU64,
+}
+
+impl Executor {
+    /// Creates an executor for `worker_count` workers.
+    pub(crate) fn new(
+        worker_count: usize,
+        slow_poll_threshold: Option<Duration>,
+        blocking: Pool,
+        clock: Option<Arc<Clock>>,
+        panic_policy: PanicPolicy,
+    ) -> Executor {
+        Executor {
+            state: AtomicUsize::new(RUNNING),
+            injector: Mutex::new(Queue::new()),
+            queued: [
+                AtomicUsize::new(0),
+                AtomicUsize::new(0),
+                AtomicUsize::new(0),
+            ],
+            workers: (0..worker_count)
+                .map(|_| WorkerState {
+                    sleeper: Mutex::new(None),
+                    local: Mutex::new(Queue::new()),
+                    pinned: Mutex::new(VecDeque::new()),
+                    busy: AtomicU64::new(0),
+                })
+                .collect(),
+            sleeping: AtomicUsize::new(0),
+            tasks: (0..SHARDS).map(|_| Mutex::new(Slab::new())).collect(),
+            live: AtomicUsize::new(0),
+            idle_lock: Mutex::new(()),
+            idle: Condvar::new(),
+            spawned: AtomicUsize::new(0),
+            completed: AtomicUsize::new(0),
+            next_worker: AtomicUsize::new(0),
+            slow_poll_threshold,
+            blocking,
+            clock,
+            panic_policy,
+        }
+    }
+
+    /// Spawns a future onto the executor with the given priority.
+    ///
+    /// Returns an error if the executor has been shut down.
+    pub(crate) fn spawn<F, T>(
+        self: &Arc<Self>,
+        future: F,
+        priority: Priority,
+    ) -> io::Result<async_task::JoinHandle<T, ()>>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let executor = self.clone();
+        self.spawn_with(future, move |runnable| {
+            executor.schedule(runnable, priority)
+        })
+    }
+
+    /// Spawns a future onto the executor, to be run only by the worker with the given index.
+    ///
+    /// Returns an error if there is no such worker or the executor has been shut down.
+    pub(crate) fn spawn_on<F, T>(
+        self: &Arc<Self>,
+        index: usize,
+        future: F,
+    ) -> io::Result<async_task::JoinHandle<T, ()>>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        if index >= self.workers.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("the runtime has no worker with index {}", index),
+            ));
+        }
+
+        let executor = self.clone();
+        self.spawn_with(future, move |runnable| {
+            executor.schedule_on(index, runnable)
+        })
+    }
+
+    /// Returns the index of the worker that should get the next pinned task.
+    pub(crate) fn next_worker(&self) -> usize {
+        self.next_worker.fetch_add(1, Ordering::Relaxed) % self.workers.len()
+    }
+
+    /// Spawns a future onto the executor, scheduling it with the given function.
+    fn spawn_with<F, T, S>(
+        self: &Arc<Self>,
+        future: F,
+        schedule: S,
+    ) -> io::Result<async_task::JoinHandle<T, ()>>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+        S: Fn(Runnable) + Send + Sync + 'static,
+    {
+        let shard = self.spawned.load(Ordering::Relaxed) % SHARDS;
+        let mut tasks = self.tasks[shard].lock().unwrap();
+        if self.state.load(Ordering::SeqCst) != RUNNING {
+            return Err(io::Error::other("the runtime has been shut down"));
+        }
+        let key = tasks.insert(None) * SHARDS + shard;
+        self.live.fetch_add(1, Ordering::SeqCst);
+        self.spawned.fetch_add(1, Ordering::Relaxed);
+
+        // Unregister the task once its future completes or gets dropped, even if it never ran.
+        let registration = Registration {
+            executor: self.clone(),
+            key,
+        };
+        let future = async move {
+            let _registration = registration;
+            future.await
+        };
+
+        let (runnable, handle) = async_task::spawn(future, schedule, ());
+
+        tasks[key / SHARDS] = Some(runnable.waker());
+        drop(tasks);
+
+        runnable.schedule();
+        Ok(handle)
+    }
+
+    /// Returns a future that runs tasks as the worker with the given index.
+    ///
+    /// The future completes when the executor stops.
+    pub(crate) fn worker(self: &Arc<Self>, index: usize) -> Worker {
+        Worker {
+            executor: self.clone(),
+            index,
+        }
+    }
+
+    /// Stops accepting new tasks and waits for the live ones to complete.
+    ///
+    /// Tasks still running after `timeout` are cancelled: the ones waiting to be woken up are
+    /// dropped right away, and the ones stuck in a poll are left to their workers until they
+    /// return. Either way, this returns once all tasks are gone or `timeout` has elapsed, and
+    /// stops the workers. Returns the number of cancelled tasks.
+    pub(crate) fn shutdown(&self, timeout: Duration) -> usize {
+        let deadline = Instant::now() + timeout;
+
+        if self
+            .state
+            .compare_exchange(RUNNING, CLOSED, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return 0;
+        }
+
+        // Tasks are registered while holding the lock of their shard, so this waits for the
+        // spawns that haven't noticed the executor got closed.
+        for shard in &self.tasks {
+            drop(shard.lock().unwrap());
+        }
+
+        // Give the live tasks a chance to complete on their own.
+        let mut cancelled = 0;
+        if !self.wait_idle(deadline) {
+            self.state.store(CANCELLING, Ordering::SeqCst);
+
+            // Reschedule the remaining tasks and drop them. Pinned tasks are dropped by their
+            // workers, since they may only be dropped on the thread they were created on.
+            let wakers = self.wakers();
+            cancelled = wakers.len();
+            for w in wakers {
+                w.wake();
+            }
+            self.notify_all();
+
+            loop {
+                self.drain(&self.injector);
+                for worker in &self.workers {
+                    self.drain(&worker.local);
+                }
+
+                let guard = self.idle_lock.lock().unwrap();
+                let now = Instant::now();
+                if self.live.load(Ordering::SeqCst) == 0 || now >= deadline {
+                    break;
+                }
+                if self.queued() == 0 {
+                    drop(self.idle.wait_timeout(guard, deadline - now).unwrap());
+                }
+            }
+        }
+
+        self.state.store(STOPPED, Ordering::SeqCst);
+        self.notify_all();
+
+        cancelled
+    }
+
+    /// Returns `true` if there are no live tasks.
+    pub(crate) fn is_idle(&self) -> bool {
+        self.live.load(Ordering::SeqCst) == 0
+    }
+
+    /// Waits until there are no live tasks, or until the deadline.
+    ///
+    /// Returns `false` if some tasks are still live.
+    fn wait_idle(&self, deadline: Instant) -> bool {
+        let mut guard = self.idle_lock.lock().unwrap();
+        while !self.is_idle() {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            guard = self.idle.wait_timeout(guard, deadline - now).unwrap().0;
+        }
+        true
+    }
+
+    /// Returns the wakers of all live tasks.
+    fn wakers(&self) -> Vec<Waker> {
+        let mut wakers = Vec::new();
+        for shard in &self.tasks {
+            let tasks = shard.lock().unwrap();
+            wakers.extend(tasks.iter().filter_map(|(_, w)| w.clone()));
+        }
+        wakers
+    }
+
+    /// Returns a snapshot of the executor's activity.
+    pub(crate) fn metrics(&self) -> Metrics {
+        // Load `completed` first so that it never exceeds `spawned`.
+        let completed_tasks = self.completed.load(Ordering::Relaxed);
+        let spawned_tasks = self.spawned.load(Ordering::Relaxed);
+
+        Metrics {
+            live_tasks: self.live.load(Ordering::SeqCst),
+            spawned_tasks,
+            completed_tasks,
+            queue_depth: self.queue_len(),
+            worker_busy_time: self
+                .workers
+                .iter()
+                .map(|w| Duration::from_nanos(w.busy.load(Ordering::Relaxed)))
+                .collect(),
+            blocking_tasks: self.blocking.live_tasks(),
+            blocking_threads: self.blocking.thread_count(),
+        }
+    }
+
+    /// Cancels all live tasks on the current thread and stops the executor.
+    ///
+    /// This is for executors driven by the current thread rather than by worker threads. Returns
+    /// the number of cancelled tasks.
+    pub(crate) fn cancel_all(&self) -> usize {
+        if self.state.swap(CANCELLING, Ordering::SeqCst) == STOPPED {
+            self.state.store(STOPPED, Ordering::SeqCst);
+            return 0;
+        }
+
+        // Reschedule the tasks and drop them.
+        let wakers = self.wakers();
+        let cancelled = wakers.len();
+        for w in wakers {
+            w.wake();
+        }
+        for index in 0..self.workers.len() {
+            self.drain_pinned(index);
+            self.drain(&self.workers[index].local);
+        }
+        self.drain(&self.injector);
+
+        self.state.store(STOPPED, Ordering::SeqCst);
+        cancelled
+    }
+
+    /// Returns the number of tasks waiting to be run, including pinned tasks.
+    pub(crate) fn queue_len(&self) -> usize {
+        let local: usize = self
+            .workers
+            .iter()
+            .map(|w| w.local.lock().unwrap().len() + w.pinned.lock().unwrap().len())
+            .sum();
+        self.injector.lock().unwrap().len() + local
+    }
+
+    /// Returns `true` if the executor has stopped.
+    pub(crate) fn is_stopped(&self) -> bool {
+        self.state.load(Ordering::SeqCst) == STOPPED
+    }
+
+    /// Runs the next task as the worker with the given index.
+    ///
+    /// Returns `false` if there was no task to run.
+    pub(crate) fn run_next(&self, index: usize) -> bool {
+        match self.pop(index) {
+            Some(runnable) => {
+                self.run(index, runnable);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Runs the task at position `n` in the queue as the worker with the given index.
+    ///
+    /// Tasks pinned to the worker come first, followed by the tasks in the shared queue. This is
+    /// for executors driven by the current thread, which only use these two queues. Returns
+    /// `false` if there are fewer than `n + 1` tasks.
+    pub(crate) fn run_nth(&self, index: usize, n: usize) -> bool {
+        let runnable = {
+            let mut pinned = self.workers[index].pinned.lock().unwrap();
+            if n < pinned.len() {
+                pinned.remove(n)
+            } else {
+                let n = n - pinned.len();
+                drop(pinned);
+                let runnable = self.injector.lock().unwrap().remove(n);
+                if let Some((_, level)) = &runnable {
+                    self.queued[*level].fetch_sub(1, Ordering::SeqCst);
+                }
+                runnable.map(|(runnable, _)| runnable)
+            }
+        };
+        match runnable {
+            Some(runnable) => {
+                self.run(index, runnable);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Runs a task as the worker with the given index, or drops it if tasks are being cancelled.
+    fn run(&self, index: usize, runnable: Runnable) {
+        if self.state.load(Ordering::SeqCst) >= CANCELLING {
+            drop(runnable);
+        } else {
+            let start = Instant::now();
+            runnable.run();
+            let busy = start.elapsed().as_nanos() as u64;
+            self.workers[index].busy.fetch_add(busy, Ordering::Relaxed);
+        }
+    }
+
+    /// Registers the waker of an idle worker, to be woken when a task gets scheduled.
+    ///
+    /// Returns `false` if the worker shouldn't go to sleep because a task got scheduled or the
+    /// executor got stopped in the meantime.
+    pub(crate) fn sleep(&self, index: usize, waker: &Waker) -> bool {
+        let worker = &self.workers[index];
+        if worker
+            .sleeper
+            .lock()
+            .unwrap()
+            .replace(waker.clone())
+            .is_none()
+        {
+            self.sleeping.fetch_add(1, Ordering::SeqCst);
+        }
+
+        // Check again in case a task got scheduled or the executor got stopped before the waker
+        // was registered.
+        let state = self.state.load(Ordering::SeqCst);
+        worker.pinned.lock().unwrap().is_empty()
+            && (state >= CANCELLING || self.queued() == 0)
+            && state != STOPPED
+    }
+
+    /// Pushes a task into a queue and wakes up a worker.
+    ///
+    /// Tasks scheduled by a worker of this executor go into its local queue, and other tasks into
+    /// the shared queue.
+    fn schedule(&self, runnable: Runnable, priority: Priority) {
+        // Count the task first, so that the count never falls below the number of queued tasks.
+        self.queued[priority.index()].fetch_add(1, Ordering::SeqCst);
+        match self.current_worker() {
+            Some(index) => self.workers[index]
+                .local
+                .lock()
+                .unwrap()
+                .push(runnable, priority),
+            None => self.injector.lock().unwrap().push(runnable, priority),
+        }
+
+        if self.state.load(Ordering::SeqCst) == CANCELLING {
+            // Let `shutdown` drop the task.
+            let _guard = self.idle_lock.lock().unwrap();
+            self.idle.notify_all();
+        }
+        self.notify();
+    }
+
+    /// Pushes a task into the queue of the worker it is pinned to and wakes the worker up.
+    fn schedule_on(&self, index: usize, runnable: Runnable) {
+        self.workers[index]
+            .pinned
+            .lock()
+            .unwrap()
+            .push_back(runnable);
+        self.wake(index);
+    }
+
+    /// Returns the index of the worker running on the current thread, if it belongs to this
+    /// executor.
+    fn current_worker(&self) -> Option<usize> {
+        WORKER.with(|worker| match worker.get() {
+            Some((executor, index)) if ptr::eq(executor, self) => Some(index),
+            _ => None,
+        })
+    }
+
+    /// Returns the number of tasks in the shared queue and in the local queues.
+    fn queued(&self) -> usize {
+        self.queued.iter().map(|n| n.load(Ordering::SeqCst)).sum()
+    }
+
+    /// Pops a task for the worker with the given index.
+    ///
+    /// Tasks pinned to the worker come first. Then, for each priority level, the worker looks in
+    /// its local queue, in the shared queue, and in the local queues of other workers.
+    fn pop(&self, index: usize) -> Option<Runnable> {
+        let worker = &self.workers[index];
+        if let Some(runnable) = worker.pinned.lock().unwrap().pop_front() {
+            return Some(runnable);
+        }
+
+        // While tasks are being cancelled, `shutdown` drops the queued tasks.
+        if self.state.load(Ordering::SeqCst) >= CANCELLING {
+            return None;
+        }
+
+        for level in 0..Priority::COUNT {
+            if self.queued[level].load(Ordering::SeqCst) == 0 {
+                continue;
+            }
+
+            let mut runnable = worker.local.lock().unwrap().pop(level);
+            if runnable.is_none() {
+                runnable = self.injector.lock().unwrap().pop(level);
+            }
+            if runnable.is_none() {
+                runnable = self.steal(index, level);
+            }
+
+            if let Some(runnable) = runnable {
+                self.queued[level].fetch_sub(1, Ordering::SeqCst);
+
+                // If there is more work, make sure another worker picks it up.
+                if self.queued() > 0 {
+                    self.notify();
+                }
+                return Some(runnable);
+            }
+        }
+        None
+    }
+
+    /// Steals about half of the tasks of a priority level from another worker's local queue.
+    ///
+    /// One of the stolen tasks is returned and the others go into the local queue of the worker
+    /// with the given index.
+    fn steal(&self, index: usize, level: usize) -> Option<Runnable> {
+        let count = self.workers.len();
+        for i in 1..count {
+            // Skip workers whose queue is in use rather than waiting for them.
+            let mut stolen = match self.workers[(index + i) % count].local.try_lock() {
+                Ok(mut local) => local.steal(level),
+                Err(_) => continue,
+            };
+
+            if let Some(runnable) = stolen.pop_front() {
+                if !stolen.is_empty() {
+                    self.workers[index].local.lock().unwrap().levels[level].extend(stolen);
+                }
+                return Some(runnable);
+            }
+        }
+        None
+    }
+
+    /// Drops the tasks in a shared or local queue.
+    fn drain(&self, queue: &Mutex<Queue>) {
+        for level in 0..Priority::COUNT {
+            loop {
+                // Release the lock before dropping the task, which may schedule other tasks.
+                let runnable = queue.lock().unwrap().pop(level);
+                match runnable {
+                    Some(runnable) => {
+                        self.queued[level].fetch_sub(1, Ordering::SeqCst);
+                        drop(runnable);
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+
+    /// Drops the tasks pinned to the worker with the given index.
+    fn drain_pinned(&self, index: usize) {
+        loop {
+            let runnable = self.workers[index].pinned.lock().unwrap().pop_front();
+            match runnable {
+                Some(runnable) => drop(runnable),
+                None => break,
+            }
+        }
+    }
+
+    /// Wakes up one sleeping worker, if any.
+    fn notify(&self) {
+        if self.sleeping.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+        for index in 0..self.workers.len() {
+            if self.wake(index) {
+                return;
+            }
+        }
+    }
+
+    /// Wakes up all sleeping workers.
+    fn notify_all(&self) {
+        for index in 0..self.workers.len() {
+            self.wake(index);
+        }
+    }
+
+    /// Wakes up the worker with the given index if it is sleeping.
+    ///
+    /// Returns `false` if the worker was awake.
+    fn wake(&self, index: usize) -> bool {
+        match self.take_sleeper(index) {
+            Some(w) => {
+                w.wake();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Takes the waker of the worker with the given index, if it is sleeping.
+    fn take_sleeper(&self, index: usize) -> Option<Waker> {
+        let waker = self.workers[index].sleeper.lock().unwrap().take();
+        if waker.is_some() {
+            self.sleeping.fetch_sub(1, Ordering::SeqCst);
+        }
+        waker
+    }
+
+    /// Removes a completed task from the set of live tasks.
+    fn unregister(&self, key: usize) {
+        self.tasks[key % SHARDS]
+            .lock()
+            .unwrap()
+            .remove(key / SHARDS);
+        self.completed.fetch_add(1, Ordering::Relaxed);
+
+        if self.live.fetch_sub(1, Ordering::SeqCst) == 1 {
+            // Take the lock so that the notification can't get lost between the check and the wait
+            // in `wait_idle`.
+            let _guard = self.idle_lock.lock().unwrap();
+            self.idle.notify_all();
+        }
+    }
+}
+
+/// Tasks that are ready to be run, with one queue per priority level.
+struct Queue {
+    levels: [VecDeque<Runnable>; Priority::COUNT],
+}
+
+impl Queue {
+    fn new() -> Queue {
+        Queue {
+            levels: [VecDeque::new(), VecDeque::new(), VecDeque::new()],
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.levels.iter().map(|q| q.len()).sum()
+    }
+
+    fn push(&mut self, runnable: Runnable, priority: Priority) {
+        self.levels[priority.index()].push_back(runnable);
+    }
+
+    /// Pops the oldest task of a priority level.
+    fn pop(&mut self, level: usize) -> Option<Runnable> {
+        self.levels[level].pop_front()
+    }
+
+    /// Takes the older half of the tasks of a priority level, rounded up.
+    fn steal(&mut self, level: usize) -> VecDeque<Runnable> {
+        let q = &mut self.levels[level];
+        let count = q.len() - q.len() / 2;
+        q.drain(..count).collect()
+    }
+
+    /// Removes the task at position `n`, with tasks ordered by priority and then by age.
+    ///
+    /// Returns the task along with its priority level.
+    fn remove(&mut self, mut n: usize) -> Option<(Runnable, usize)> {
+        for (level, q) in self.levels.iter_mut().enumerate() {
+            if n < q.len() {
+                return q.remove(n).map(|runnable| (runnable, level));
+            }
+            n -= q.len();
+        }
+        None
+    }
+}
+
+/// Keeps a task registered as live in the executor until it is dropped.
+struct Registration {
+    executor: Arc<Executor>,
+    key: usize,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.executor.unregister(self.key);
+    }
+}
+
+/// A future that runs tasks from the executor until it is stopped.
+pub(crate) struct Worker {
+    executor: Arc<Executor>,
+    index: usize,
+}
+
+impl Future for Worker {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let executor = &self.executor;
+
+        // The worker is awake, so it doesn't need to be notified anymore.
+        executor.take_sleeper(self.index);
+
+        // Tasks scheduled while the worker runs go into its local queue.
+        let previous = WORKER.with(|w| w.replace(Some((Arc::as_ptr(executor), self.index))));
+        defer! {
+            WORKER.with(|w| w.set(previous));
+        }
+
+        for _ in 0..YIELD_INTERVAL {
+            if executor.is_stopped() {
+                // Drop the tasks left behind by cancellation.
+                executor.drain_pinned(self.index);
+                executor.drain(&executor.workers[self.index].local);
+                executor.drain(&executor.injector);
+                return Poll::Ready(());
+            }
+
+            if !executor.run_next(self.index) && executor.sleep(self.index, cx.waker()) {
+                return Poll::Pending;
+            }
+        }
+
+        // Yield to give the reactor a chance to run.
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+/// A snapshot of a runtime's activity.
+///
+/// Metrics are obtained with [`rt::metrics`] or [`Runtime::metrics`]. The values are read one
+/// after the other while the runtime keeps running, so they are not guaranteed to be consistent
+/// with each other.
+///
+/// [`rt::metrics`]: fn.metrics.html
+/// [`Runtime::metrics`]: struct.Runtime.html#method.metrics
+#[derive(Clone, Debug, Default)]
+pub struct Metrics {
+    pub(crate) live_tasks: usize,
+    pub(crate) spawned_tasks: usize,
+    pub(crate) completed_tasks: usize,
+    pub(crate) queue_depth: usize,
+    pub(crate) worker_busy_time: Vec<Duration>,
+    pub(crate) blocking_tasks: usize,
+    pub(crate) blocking_threads: usize,
+}
+
+impl Metrics {
+    /// Returns the number of tasks that have been spawned and have not completed yet.
+    pub fn live_tasks(&self) -> usize {
+        self.live_tasks
+    }
+
+    /// Returns the total number of tasks spawned since the runtime was started.
+    pub fn spawned_tasks(&self) -> usize {
+        self.spawned_tasks
+    }
+
+    /// Returns the total number of tasks that have completed or been cancelled since the runtime
+    /// was started.
+    pub fn completed_tasks(&self) -> usize {
+        self.completed_tasks
+    }
+
+    /// Returns the number of tasks that are ready to run and waiting for a worker.
+    ///
+    /// A queue depth that stays high means the workers can't keep up, which is usually caused
+    /// by tasks that block or run for too long between yields.
+    pub fn queue_depth(&self) -> usize {
+        self.queue_depth
+    }
+
+    /// Returns the total time each worker has spent running tasks, indexed by worker.
+    ///
+    /// Sampling this value periodically gives the utilization of every worker.
+    pub fn worker_busy_time(&self) -> &[Duration] {
+        &self.worker_busy_time
+    }
+
+    /// Returns the number of workers.
+    pub fn worker_count(&self) -> usize {
+        self.worker_busy_time.len()
+    }
+
+    /// Returns the number of blocking tasks that have been spawned and have not completed yet.
+    ///
+    /// This includes the tasks waiting for a thread of the blocking pool to become available.
+    pub fn blocking_tasks(&self) -> usize {
+        self.blocking_tasks
+    }
+
+    /// Returns the number of threads in the blocking pool.
+    pub fn blocking_threads(&self) -> usize {
+        self.blocking_threads
+    }
+}
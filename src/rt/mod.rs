@@ -1,34 +1,330 @@
 //! The runtime.
+//!
+//! The runtime is a pool of threads that executes spawned tasks. It is started lazily the first
+//! time a task is spawned, and is configured from the environment variables described in the
+//! [crate documentation](../index.html#runtime-configuration).
+//!
+//! Applications that embed async-std and want to configure the runtime programmatically can do
+//! so with a [`Builder`], as long as the configuration is installed before the runtime is first
+//! used.
+//!
+//! [`Builder`]: struct.Builder.html
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "unstable")]
+//! # {
+//! use async_std::rt;
+//! use async_std::task;
+//!
+//! rt::Builder::new()
+//!     .thread_count(2)
+//!     .thread_name("my-app/runtime".to_string())
+//!     .init()
+//!     .expect("the runtime has already been started");
+//!
+//! task::block_on(async {
+//!     task::spawn(async { 1 + 2 }).await.unwrap();
+//! });
+//! # }
+//! ```
+//!
+//! # Shutting down
+//!
+//! The runtime threads keep running until the process exits, unless the runtime is explicitly
+//! stopped with [`shutdown`]. This is useful in tests and in hosts that load and unload async
+//! code, and want to make sure no runtime threads are left behind.
+//!
+//! [`shutdown`]: fn.shutdown.html
+//!
+//! # Multiple runtimes
+//!
+//! Besides the global runtime, any number of isolated [`Runtime`]s can be created. A runtime
+//! owns its threads, and tasks spawned from within one of its tasks, or from a thread that
+//! [entered] it, run on that runtime. This is useful to keep latency-sensitive tasks away from
+//! long-running batch work.
+//!
+//! [`Runtime`]: struct.Runtime.html
+//! [entered]: struct.Runtime.html#method.enter
+//!
+//! # Testing
+//!
+//! A [`TestRuntime`] runs all of its tasks on a single thread, in a reproducible order, and
+//! measures time with a virtual clock. Tests that involve timeouts or intervals can use it to
+//! complete instantly instead of waiting in real time. It can also run tasks in a random order
+//! determined by a seed, to shake out race conditions.
+//!
+//! [`TestRuntime`]: struct.TestRuntime.html
+//!
+//! # Monitoring
+//!
+//! A snapshot of the runtime's activity, such as the number of live tasks and the time workers
+//! spend running them, can be taken at any time with [`metrics`]. Tasks that block the runtime
+//! threads can be reported by setting a [slow poll threshold].
+//!
+//! [`metrics`]: fn.metrics.html
+//! [slow poll threshold]: struct.Builder.html#method.slow_poll_threshold
 
-use std::env;
-use std::thread;
+#![cfg_attr(not(feature = "unstable"), allow(dead_code))]
 
-use once_cell::sync::Lazy;
+use std::any::Any;
+use std::cell::RefCell;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::future;
+use once_cell::sync::OnceCell;
 
-/// Dummy runtime struct.
-pub struct Runtime {}
+use crate::io;
+use crate::task::{Output, Priority, Task};
+
+pub use builder::Builder;
+pub use metrics::Metrics;
+pub use panic_policy::PanicPolicy;
+pub use runtime::Runtime;
+#[cfg(feature = "unstable")]
+pub use test_runtime::TestRuntime;
+
+pub(crate) use blocking::Pool;
+pub(crate) use clock::{Clock, Timer};
+pub(crate) use executor::Executor;
+
+mod blocking;
+mod builder;
+mod clock;
+mod executor;
+mod metrics;
+mod panic_policy;
+mod runtime;
+#[cfg(feature = "unstable")]
+mod test_runtime;
 
 /// The global runtime.
-pub static RUNTIME: Lazy<Runtime> = Lazy::new(|| {
-    // Create an executor thread pool.
+static RUNTIME: OnceCell<Runtime> = OnceCell::new();
+
+thread_local! {
+    /// The executor of the runtime entered by the current thread, if any.
+    static CURRENT: RefCell<Option<Arc<Executor>>> = const { RefCell::new(None) };
+}
+
+/// Returns the global runtime, starting it with the default configuration if necessary.
+pub(crate) fn runtime() -> &'static Runtime {
+    RUNTIME.get_or_init(|| {
+        Builder::new()
+            .build()
+            .expect("cannot start a runtime thread")
+    })
+}
+
+/// Spawns a future onto the runtime entered by the current thread, or the global runtime.
+pub(crate) fn spawn<F, T>(
+    future: F,
+    priority: Priority,
+) -> io::Result<async_task::JoinHandle<T, ()>>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    executor().spawn(future, priority)
+}
+
+/// Spawns a future onto a worker of the runtime entered by the current thread, or the global
+/// runtime.
+///
+/// The future is only ever run by that worker. If no worker is given, one is picked in a
+/// round-robin fashion.
+pub(crate) fn spawn_on<F, T>(
+    worker: Option<usize>,
+    future: F,
+) -> io::Result<async_task::JoinHandle<T, ()>>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    let executor = executor();
+    let worker = worker.unwrap_or_else(|| executor.next_worker());
+    executor.spawn_on(worker, future)
+}
+
+/// Spawns a blocking task onto the runtime entered by the current thread, or the global runtime.
+///
+/// A panic of the task is caught and handled according to the runtime's panic policy. The result
+/// is stored in `output`.
+pub(crate) fn spawn_blocking<F, T>(
+    task: Task,
+    output: Output<T>,
+    f: F,
+) -> async_task::JoinHandle<(), ()>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let executor = executor();
+    let entered = executor.clone();
+    executor.blocking.spawn(move || {
+        // Tasks spawned and timers created by the blocking task belong to the same runtime.
+        enter(&entered, || {
+            let res = panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+                entered.panic_policy.handle(&task, &*payload);
+                payload
+            });
+            output.set(res);
+        })
+    })
+}
+
+/// Returns the executor of the runtime entered by the current thread, or of the global runtime.
+fn executor() -> Arc<Executor> {
+    match CURRENT.with(|current| current.borrow().clone()) {
+        Some(executor) => executor,
+        None => runtime().executor.clone(),
+    }
+}
 
-    let thread_count = env::var("ASYNC_STD_THREAD_COUNT")
-        .map(|env| {
-            env.parse()
-                .expect("ASYNC_STD_THREAD_COUNT must be a number")
+/// Handles a panic of `task` according to the panic policy of the runtime entered by the current
+/// thread, or the default policy.
+pub(crate) fn handle_panic(task: &Task, payload: &(dyn Any + Send)) {
+    let policy = CURRENT
+        .try_with(|current| {
+            current
+                .borrow()
+                .as_ref()
+                .map(|executor| executor.panic_policy.clone())
         })
-        .unwrap_or_else(|_| num_cpus::get())
-        .max(1);
+        .unwrap_or(None);
 
-    let thread_name = env::var("ASYNC_STD_THREAD_NAME").unwrap_or("async-std/runtime".to_string());
+    policy.unwrap_or_default().handle(task, payload);
+}
+
+/// Returns the slow poll threshold of the runtime entered by the current thread, if any.
+pub(crate) fn slow_poll_threshold() -> Option<Duration> {
+    CURRENT
+        .try_with(|current| {
+            current
+                .borrow()
+                .as_ref()
+                .and_then(|executor| executor.slow_poll_threshold)
+        })
+        .unwrap_or(None)
+}
+
+/// Returns the virtual clock of the runtime entered by the current thread, if any.
+pub(crate) fn clock() -> Option<Arc<Clock>> {
+    CURRENT
+        .try_with(|current| {
+            current
+                .borrow()
+                .as_ref()
+                .and_then(|executor| executor.clock.clone())
+        })
+        .unwrap_or(None)
+}
+
+/// Returns the current time, as measured by the virtual clock of the runtime entered by the
+/// current thread, or by the real clock if that runtime doesn't have one.
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+pub(crate) fn now() -> Instant {
+    clock().map_or_else(Instant::now, |clock| clock.now())
+}
+
+/// Returns `true` if the current thread has entered the runtime of `executor`.
+pub(crate) fn is_entered(executor: &Arc<Executor>) -> bool {
+    CURRENT
+        .try_with(|current| match &*current.borrow() {
+            Some(current) => Arc::ptr_eq(current, executor),
+            None => false,
+        })
+        .unwrap_or(false)
+}
+
+/// Makes the current thread enter the runtime of `executor` for the duration of a closure.
+pub(crate) fn enter<F, R>(executor: &Arc<Executor>, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    CURRENT.with(|current| {
+        let old = current.replace(Some(executor.clone()));
+        defer! {
+            current.replace(old);
+        }
+        f()
+    })
+}
+
+/// Shuts down the global runtime.
+///
+/// Once this function is called, spawning new tasks fails. It waits for the tasks that are still
+/// alive to complete for up to `timeout`, cancels the ones that didn't, and then waits for the
+/// runtime threads to exit.
+///
+/// Cancelled tasks are dropped the next time they would be polled. A task that is stuck inside a
+/// poll can't be cancelled until the poll returns, so once `timeout` has elapsed this function
+/// returns without waiting for it, and the thread running it exits on its own afterwards.
+///
+/// Returns the number of tasks that had to be cancelled. If the runtime was never started, this
+/// function does nothing and returns zero.
+///
+/// Blocking tasks spawned with [`task::spawn_blocking`] keep running to completion, and new ones
+/// can still be spawned, but the threads running them exit as soon as they are idle instead of
+/// waiting for more work.
+///
+/// [`task::spawn_blocking`]: ../task/fn.spawn_blocking.html
+///
+/// This function blocks the current thread. When called from within a task running on the
+/// runtime, that task counts as live and gets cancelled, so the call always takes `timeout`.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "unstable")]
+/// # {
+/// use std::time::Duration;
+///
+/// use async_std::rt;
+/// use async_std::task;
+///
+/// task::spawn(async {
+///     task::sleep(Duration::from_millis(100)).await;
+/// });
+///
+/// let cancelled = rt::shutdown(Duration::from_secs(1));
+/// assert_eq!(cancelled, 0);
+/// # }
+/// ```
+pub fn shutdown(timeout: Duration) -> usize {
+    match RUNTIME.get() {
+        Some(rt) => rt.stop(timeout),
+        None => 0,
+    }
+}
 
-    for _ in 0..thread_count {
-        thread::Builder::new()
-            .name(thread_name.clone())
-            .spawn(|| smol::run(future::pending::<()>()))
-            .expect("cannot start a runtime thread");
+/// Returns a snapshot of the global runtime's activity.
+///
+/// If the runtime was never started, all task counts are zero and there are no workers. Calling
+/// this function doesn't start the runtime.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "unstable")]
+/// # {
+/// use async_std::rt;
+/// use async_std::task;
+///
+/// task::block_on(async {
+///     task::spawn(async { 1 + 2 }).await.unwrap();
+/// });
+///
+/// let metrics = rt::metrics();
+/// assert!(metrics.spawned_tasks() >= 1);
+/// println!("{} tasks waiting to run", metrics.queue_depth());
+/// # }
+/// ```
+pub fn metrics() -> Metrics {
+    match RUNTIME.get() {
+        Some(rt) => rt.metrics(),
+        None => Metrics::default(),
     }
-    Runtime {}
-});
+}
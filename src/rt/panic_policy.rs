@@ -0,0 +1,94 @@
+use std::any::Any;
+use std::fmt;
+use std::process;
+use std::sync::Arc;
+
+use crate::task::Task;
+use crate::utils;
+
+/// A callback invoked when a task panics.
+type Callback = Arc<dyn Fn(&Task, &(dyn Any + Send)) + Send + Sync>;
+
+/// What a runtime does when one of its tasks panics.
+///
+/// Panics are caught at the boundary of the task, so they never take a runtime thread down. The
+/// [`JoinHandle`] of the task still observes the panic: awaiting it returns the panic as a
+/// [`JoinError`].
+///
+/// The policy is configured with [`Builder::panic_policy`], and defaults to [`Log`].
+///
+/// [`JoinHandle`]: ../task/struct.JoinHandle.html
+/// [`JoinError`]: ../task/struct.JoinError.html
+/// [`Builder::panic_policy`]: struct.Builder.html#method.panic_policy
+/// [`Log`]: #variant.Log
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "unstable")]
+/// # {
+/// use async_std::rt::{self, PanicPolicy};
+///
+/// let runtime = rt::Builder::new()
+///     .panic_policy(PanicPolicy::callback(|task, _| {
+///         eprintln!("task {} panicked", task.id());
+///     }))
+///     .build()
+///     .expect("cannot start the runtime");
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub enum PanicPolicy {
+    /// Aborts the process, after logging the panic.
+    Abort,
+
+    /// Logs the panic as an error, along with the id and name of the task, and keeps running
+    /// other tasks.
+    #[default]
+    Log,
+
+    /// Invokes a callback with the task that panicked and the panic payload, and keeps running
+    /// other tasks.
+    Callback(Callback),
+}
+
+impl PanicPolicy {
+    /// Creates a policy that invokes `f` with the task that panicked and the panic payload.
+    pub fn callback<F>(f: F) -> PanicPolicy
+    where
+        F: Fn(&Task, &(dyn Any + Send)) + Send + Sync + 'static,
+    {
+        PanicPolicy::Callback(Arc::new(f))
+    }
+
+    /// Handles a panic of `task`.
+    pub(crate) fn handle(&self, task: &Task, payload: &(dyn Any + Send)) {
+        match self {
+            PanicPolicy::Abort => {
+                log_panic(task, payload);
+                process::abort();
+            }
+            PanicPolicy::Log => log_panic(task, payload),
+            PanicPolicy::Callback(f) => f(task, payload),
+        }
+    }
+}
+
+impl fmt::Debug for PanicPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PanicPolicy::Abort => f.pad("Abort"),
+            PanicPolicy::Log => f.pad("Log"),
+            PanicPolicy::Callback(_) => f.pad("Callback(..)"),
+        }
+    }
+}
+
+/// Logs a panic of `task` as an error.
+fn log_panic(task: &Task, payload: &(dyn Any + Send)) {
+    kv_log_macro::error!("task panicked", {
+        task_id: task.id().0,
+        task_name: task.name().unwrap_or(""),
+        message: utils::panic_message(payload),
+    });
+}
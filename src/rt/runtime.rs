@@ -0,0 +1,160 @@
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::io;
+use crate::rt::{self, Builder, Executor, Metrics};
+use crate::task::{self, JoinHandle};
+
+/// A runtime, made of an executor and the threads running it.
+///
+/// The global runtime used by [`task::spawn`] is started automatically, but additional runtimes
+/// can be created with [`Runtime::new`] or [`Builder::build`]. Each runtime owns its threads, so
+/// tasks spawned onto one runtime can't be slowed down by tasks running on another.
+///
+/// Dropping a runtime cancels all of its tasks and stops its threads. Use [`shutdown`] to give
+/// the tasks a chance to complete first.
+///
+/// [`task::spawn`]: ../task/fn.spawn.html
+/// [`Runtime::new`]: #method.new
+/// [`Builder::build`]: struct.Builder.html#method.build
+/// [`shutdown`]: #method.shutdown
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "unstable")]
+/// # {
+/// use async_std::rt::Runtime;
+/// use async_std::task;
+///
+/// let runtime = Runtime::new().expect("cannot start the runtime");
+///
+/// let handle = runtime.spawn(async {
+///     // Tasks spawned from within this task also run on `runtime`.
+///     task::spawn(async { 1 + 2 }).await.unwrap()
+/// });
+///
+/// assert_eq!(runtime.block_on(handle).unwrap(), 3);
+/// # }
+/// ```
+pub struct Runtime {
+    /// The executor running spawned tasks.
+    pub(crate) executor: Arc<Executor>,
+
+    /// Handles to the runtime threads.
+    pub(crate) threads: Mutex<Vec<thread::JoinHandle<()>>>,
+}
+
+impl Runtime {
+    /// Creates a new runtime with the default settings.
+    ///
+    /// This is equivalent to `Builder::new().build()`.
+    pub fn new() -> io::Result<Runtime> {
+        Builder::new().build()
+    }
+
+    /// Spawns a task onto this runtime.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the runtime has been shut down.
+    pub fn spawn<F, T>(&self, future: F) -> JoinHandle<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.enter(|| task::spawn(future))
+    }
+
+    /// Blocks the current thread on a future, spawning its tasks onto this runtime.
+    ///
+    /// The future itself runs on the current thread, as with [`task::block_on`], but every task
+    /// it spawns runs on this runtime.
+    ///
+    /// [`task::block_on`]: ../task/fn.block_on.html
+    pub fn block_on<F, T>(&self, future: F) -> T
+    where
+        F: Future<Output = T>,
+    {
+        self.enter(|| task::block_on(future))
+    }
+
+    /// Enters this runtime for the duration of a closure.
+    ///
+    /// Tasks spawned by the closure with [`task::spawn`] run on this runtime rather than on the
+    /// global one.
+    ///
+    /// [`task::spawn`]: ../task/fn.spawn.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "unstable")]
+    /// # {
+    /// use async_std::rt::Runtime;
+    /// use async_std::task;
+    ///
+    /// let runtime = Runtime::new().expect("cannot start the runtime");
+    ///
+    /// let handle = runtime.enter(|| task::spawn(async { 1 + 2 }));
+    /// assert_eq!(task::block_on(handle).unwrap(), 3);
+    /// # }
+    /// ```
+    pub fn enter<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        rt::enter(&self.executor, f)
+    }
+
+    /// Returns a snapshot of this runtime's activity.
+    ///
+    /// This works like [`rt::metrics`], but for this runtime instead of the global one.
+    ///
+    /// [`rt::metrics`]: fn.metrics.html
+    pub fn metrics(&self) -> Metrics {
+        self.executor.metrics()
+    }
+
+    /// Shuts this runtime down.
+    ///
+    /// This works like [`rt::shutdown`], but for this runtime instead of the global one.
+    ///
+    /// [`rt::shutdown`]: fn.shutdown.html
+    pub fn shutdown(self, timeout: Duration) -> usize {
+        self.stop(timeout)
+    }
+
+    /// Stops the executor and the blocking pool, and joins the runtime threads.
+    ///
+    /// If some tasks are still stuck in a poll, or if the runtime is stopped from one of its own
+    /// threads, which can't join itself, the threads are detached instead and exit on their own.
+    pub(crate) fn stop(&self, timeout: Duration) -> usize {
+        let cancelled = self.executor.shutdown(timeout);
+        self.executor.blocking.shutdown();
+
+        let threads: Vec<_> = self.threads.lock().unwrap().drain(..).collect();
+        if self.executor.is_idle() && !rt::is_entered(&self.executor) {
+            for handle in threads {
+                // A thread that panicked has already stopped.
+                let _ = handle.join();
+            }
+        }
+        cancelled
+    }
+}
+
+impl Drop for Runtime {
+    fn drop(&mut self) {
+        self.stop(Duration::from_secs(0));
+    }
+}
+
+impl fmt::Debug for Runtime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Runtime { .. }")
+    }
+}
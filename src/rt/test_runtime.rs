@@ -0,0 +1,399 @@
+use std::fmt;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::{Duration, Instant};
+
+use crate::rt::{self, Builder, Clock, Executor, PanicPolicy};
+use crate::task::{self, JoinHandle};
+use crate::utils;
+
+/// The number of steps the driver takes before yielding back to the reactor.
+const YIELD_INTERVAL: usize = 64;
+
+/// A single-threaded runtime for tests, with a virtual clock.
+///
+/// All tasks spawned onto a test runtime run on the thread that calls [`block_on`], one at a time
+/// and in the order they were scheduled, which makes tests that involve several tasks
+/// reproducible.
+///
+/// Timers created on a test runtime, such as the ones behind [`task::sleep`],
+/// [`future::timeout`], [`io::timeout`] and [`stream::interval`], are measured by a virtual clock
+/// instead of the real one. The clock only moves forward when it is [advanced] explicitly, or,
+/// unless [auto-advance] is disabled, when all tasks are waiting and some of them wait on a
+/// timer. In that case, the clock jumps straight to the next deadline, so tests involving long
+/// timeouts complete instantly.
+///
+/// Timers created outside of the test runtime, for example before calling `block_on`, keep using
+/// the real clock.
+///
+/// # Randomized scheduling
+///
+/// A test runtime created with [`with_seed`] picks the next task to run at random instead of in
+/// order. Running a test with many different seeds explores many different interleavings of its
+/// tasks, which helps finding race conditions. The choices only depend on the seed, so a failure
+/// can be replayed by running the test again with the seed that caused it, which is added to the
+/// panic message when [`block_on`] panics.
+///
+/// [`with_seed`]: #method.with_seed
+/// [`block_on`]: #method.block_on
+/// [`task::sleep`]: ../task/fn.sleep.html
+/// [`future::timeout`]: ../future/fn.timeout.html
+/// [`io::timeout`]: ../io/fn.timeout.html
+/// [`stream::interval`]: ../stream/fn.interval.html
+/// [advanced]: #method.advance
+/// [auto-advance]: #method.set_auto_advance
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use async_std::future;
+/// use async_std::rt::TestRuntime;
+///
+/// let runtime = TestRuntime::new();
+/// let start = runtime.now();
+///
+/// // Completes instantly, without waiting for an hour.
+/// let res = runtime.block_on(future::timeout(
+///     Duration::from_secs(3600),
+///     future::pending::<()>(),
+/// ));
+///
+/// assert!(res.is_err());
+/// assert_eq!(runtime.now() - start, Duration::from_secs(3600));
+/// ```
+pub struct TestRuntime {
+    /// The executor running spawned tasks.
+    executor: Arc<Executor>,
+
+    /// The virtual clock.
+    clock: Arc<Clock>,
+
+    /// Whether the clock is advanced automatically when all tasks are waiting.
+    auto_advance: AtomicBool,
+
+    /// The seed of the random number generator, if tasks are scheduled in random order.
+    seed: Option<u64>,
+
+    /// Picks the next task to run when tasks are scheduled in random order.
+    rng: Mutex<Rng>,
+}
+
+impl TestRuntime {
+    /// Creates a new test runtime.
+    ///
+    /// Tasks run in the order they are scheduled.
+    pub fn new() -> TestRuntime {
+        TestRuntime::create(None)
+    }
+
+    /// Creates a new test runtime that runs tasks in a random order determined by `seed`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use async_std::rt::TestRuntime;
+    /// use async_std::task;
+    ///
+    /// for seed in 0..10 {
+    ///     let runtime = TestRuntime::with_seed(seed);
+    ///     let log = Arc::new(Mutex::new(Vec::new()));
+    ///
+    ///     runtime.block_on(async {
+    ///         let a = task::spawn({
+    ///             let log = log.clone();
+    ///             async move { log.lock().unwrap().push('a') }
+    ///         });
+    ///         let b = task::spawn({
+    ///             let log = log.clone();
+    ///             async move { log.lock().unwrap().push('b') }
+    ///         });
+    ///         a.await.unwrap();
+    ///         b.await.unwrap();
+    ///     });
+    ///
+    ///     // Depending on the seed, `a` or `b` runs first.
+    ///     assert_eq!(log.lock().unwrap().len(), 2);
+    /// }
+    /// ```
+    pub fn with_seed(seed: u64) -> TestRuntime {
+        TestRuntime::create(Some(seed))
+    }
+
+    fn create(seed: Option<u64>) -> TestRuntime {
+        let clock = Arc::new(Clock::new());
+        let blocking = Builder::new().blocking_pool();
+
+        TestRuntime {
+            executor: Arc::new(Executor::new(
+                1,
+                None,
+                blocking,
+                Some(clock.clone()),
+                PanicPolicy::default(),
+            )),
+            clock,
+            auto_advance: AtomicBool::new(true),
+            seed,
+            rng: Mutex::new(Rng(seed.unwrap_or(0))),
+        }
+    }
+
+    /// Returns the seed that determines the order of tasks, if they run in random order.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Spawns a task onto this runtime.
+    ///
+    /// The task only runs while the current thread, or another one, is inside [`block_on`].
+    ///
+    /// [`block_on`]: #method.block_on
+    pub fn spawn<F, T>(&self, future: F) -> JoinHandle<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.enter(|| task::spawn(future))
+    }
+
+    /// Blocks the current thread on a future, running the tasks of this runtime meanwhile.
+    pub fn block_on<F, T>(&self, future: F) -> T
+    where
+        F: Future<Output = T>,
+    {
+        pin_utils::pin_mut!(future);
+
+        let signal = Arc::new(Signal {
+            woken: AtomicBool::new(true),
+            driver: Mutex::new(None),
+        });
+        let driver = Driver {
+            runtime: self,
+            future,
+            waker: Waker::from(signal.clone()),
+            signal,
+        };
+
+        self.enter(|| {
+            // Make the randomness used by combinators like `Stream::merge` reproducible too.
+            if self.seed.is_some() {
+                utils::seed_random(self.rng.lock().unwrap().next() as u32);
+            }
+            let res = panic::catch_unwind(AssertUnwindSafe(|| task::block_on(driver)));
+            match (res, self.seed) {
+                (Ok(val), _) => val,
+                (Err(payload), Some(seed)) => panic!(
+                    "the test runtime panicked with seed {}: {}",
+                    seed,
+                    utils::panic_message(&*payload)
+                ),
+                (Err(payload), None) => panic::resume_unwind(payload),
+            }
+        })
+    }
+
+    /// Enters this runtime for the duration of a closure.
+    ///
+    /// Tasks spawned and timers created by the closure belong to this runtime.
+    pub fn enter<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        rt::enter(&self.executor, f)
+    }
+
+    /// Moves the virtual clock forward.
+    ///
+    /// Timers whose deadline has passed complete, and the tasks waiting on them run the next time
+    /// the runtime gets to schedule them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use async_std::rt::TestRuntime;
+    /// use async_std::task;
+    ///
+    /// let runtime = TestRuntime::new();
+    /// runtime.set_auto_advance(false);
+    ///
+    /// runtime.block_on(async {
+    ///     let handle = task::spawn(task::sleep(Duration::from_secs(10)));
+    ///     task::yield_now().await;
+    ///
+    ///     runtime.advance(Duration::from_secs(10));
+    ///     handle.await.unwrap();
+    /// });
+    /// ```
+    pub fn advance(&self, dur: Duration) {
+        self.clock.advance(dur);
+    }
+
+    /// Returns the current time of the virtual clock.
+    ///
+    /// The virtual clock starts at the real time of the runtime's creation.
+    pub fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// Enables or disables advancing the virtual clock automatically.
+    ///
+    /// When enabled, which is the default, the clock jumps to the next timer's deadline whenever
+    /// all tasks are waiting and no blocking task is running. When disabled, the clock only moves
+    /// when [`advance`] is called.
+    ///
+    /// [`advance`]: #method.advance
+    pub fn set_auto_advance(&self, enabled: bool) {
+        self.auto_advance.store(enabled, Ordering::SeqCst);
+    }
+}
+
+impl Default for TestRuntime {
+    fn default() -> TestRuntime {
+        TestRuntime::new()
+    }
+}
+
+impl Drop for TestRuntime {
+    fn drop(&mut self) {
+        self.executor.cancel_all();
+        self.executor.blocking.shutdown();
+    }
+}
+
+impl fmt::Debug for TestRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("TestRuntime { .. }")
+    }
+}
+
+/// Records wake-ups of the future passed to `block_on`.
+struct Signal {
+    /// Set when the future is woken.
+    woken: AtomicBool,
+
+    /// The waker of the driver, woken along with the future.
+    driver: Mutex<Option<Waker>>,
+}
+
+impl Wake for Signal {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::SeqCst);
+        if let Some(w) = self.driver.lock().unwrap().as_ref() {
+            w.wake_by_ref();
+        }
+    }
+}
+
+/// A future that polls the future passed to `block_on` and runs tasks in between.
+struct Driver<'a, F> {
+    runtime: &'a TestRuntime,
+    future: Pin<&'a mut F>,
+    waker: Waker,
+    signal: Arc<Signal>,
+}
+
+impl<F: Future> Driver<'_, F> {
+    /// Polls the future passed to `block_on`.
+    fn poll_future(&mut self) -> Poll<F::Output> {
+        self.signal.woken.store(false, Ordering::SeqCst);
+        let cx = &mut Context::from_waker(&self.waker);
+        self.future.as_mut().poll(cx)
+    }
+}
+
+impl<F: Future> Future for Driver<'_, F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let runtime = this.runtime;
+        *this.signal.driver.lock().unwrap() = Some(cx.waker().clone());
+
+        for _ in 0..YIELD_INTERVAL {
+            let scheduled = runtime.executor.queue_len();
+            let woken = this.signal.woken.load(Ordering::SeqCst);
+
+            if runtime.seed.is_some() && (scheduled > 0 || woken) {
+                // Pick one of the scheduled tasks or the future passed to `block_on` at random.
+                let n = runtime
+                    .rng
+                    .lock()
+                    .unwrap()
+                    .below(scheduled + woken as usize);
+                if n < scheduled {
+                    runtime.executor.run_nth(0, n);
+                } else if let Poll::Ready(val) = this.poll_future() {
+                    return Poll::Ready(val);
+                }
+                continue;
+            }
+
+            if woken {
+                if let Poll::Ready(val) = this.poll_future() {
+                    return Poll::Ready(val);
+                }
+            }
+
+            // Run the tasks scheduled so far, so that tasks scheduled later, including the future
+            // passed to `block_on`, wait for their turn.
+            let scheduled = runtime.executor.queue_len();
+            for _ in 0..scheduled {
+                runtime.executor.run_next(0);
+            }
+            if scheduled > 0 || this.signal.woken.load(Ordering::SeqCst) {
+                continue;
+            }
+
+            // Everything is waiting, so let time pass.
+            if runtime.auto_advance.load(Ordering::SeqCst)
+                && runtime.executor.blocking.live_tasks() == 0
+                && runtime.clock.advance_to_next()
+            {
+                continue;
+            }
+
+            if runtime.executor.sleep(0, cx.waker()) && !this.signal.woken.load(Ordering::SeqCst) {
+                return Poll::Pending;
+            }
+        }
+
+        // Yield to give the reactor a chance to run.
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// A SplitMix64 random number generator.
+///
+/// Source: http://prng.di.unimi.it/splitmix64.c
+struct Rng(u64);
+
+impl Rng {
+    /// Generates a random number.
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Generates a random number in `0..n`.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
@@ -25,7 +25,7 @@ use crate::sync::{Condvar,Mutex};
 /// }
 /// // Wait for the other futures to finish.
 /// for handle in handles {
-///     handle.await;
+///     handle.await.unwrap();
 /// }
 /// # });
 /// ```
@@ -124,7 +124,7 @@ impl Barrier {
     /// }
     /// // Wait for the other futures to finish.
     /// for handle in handles {
-    ///     handle.await;
+    ///     handle.await.unwrap();
     /// }
     /// # });
     /// ```
@@ -0,0 +1,471 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::future::Future;
+use std::pin::Pin;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::sync::WakerSet;
+use crate::task::{Context, Poll};
+
+/// Creates a broadcast channel, where every receiver sees every message.
+///
+/// The channel keeps the last `cap` messages. Sending never waits: when the channel is full, the
+/// oldest message is dropped to make room for the new one. A receiver that falls behind so far
+/// that messages it hasn't seen yet are dropped gets a [`Lagged`] error telling it how many
+/// messages it missed, and then continues with the oldest message that is still kept.
+///
+/// More receivers can be created with [`BroadcastSender::subscribe`] or by cloning a receiver. A
+/// new receiver sees the messages sent after it was created. When all senders are dropped, the
+/// channel becomes closed, and receivers get a [`Closed`] error once they have seen all the
+/// remaining messages.
+///
+/// [`Lagged`]: enum.BroadcastRecvError.html#variant.Lagged
+/// [`Closed`]: enum.BroadcastRecvError.html#variant.Closed
+/// [`BroadcastSender::subscribe`]: struct.BroadcastSender.html#method.subscribe
+///
+/// # Panics
+///
+/// If `cap` is zero, this function will panic.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::broadcast;
+/// use async_std::task;
+///
+/// let (s, mut r1) = broadcast(16);
+/// let mut r2 = s.subscribe();
+///
+/// let handle = task::spawn(async move {
+///     assert_eq!(r2.recv().await.unwrap(), 1);
+///     assert_eq!(r2.recv().await.unwrap(), 2);
+/// });
+///
+/// s.send(1).unwrap();
+/// s.send(2).unwrap();
+///
+/// assert_eq!(r1.recv().await.unwrap(), 1);
+/// assert_eq!(r1.recv().await.unwrap(), 2);
+/// handle.await.unwrap();
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn broadcast<T: Clone>(cap: usize) -> (BroadcastSender<T>, BroadcastReceiver<T>) {
+    assert!(cap > 0, "capacity must be positive");
+
+    let channel = Arc::new(Channel {
+        state: Mutex::new(State {
+            buffer: VecDeque::with_capacity(cap),
+            head: 0,
+            receiver_count: 1,
+            closed: false,
+        }),
+        cap,
+        sender_count: AtomicUsize::new(1),
+        wakers: WakerSet::new(),
+    });
+    let s = BroadcastSender {
+        channel: channel.clone(),
+    };
+    let r = BroadcastReceiver { channel, next: 0 };
+    (s, r)
+}
+
+/// The sending side of a broadcast channel.
+///
+/// This struct is created by the [`broadcast`] function. See its documentation for more.
+///
+/// [`broadcast`]: fn.broadcast.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct BroadcastSender<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> BroadcastSender<T> {
+    /// Sends a message to all receivers.
+    ///
+    /// Returns the number of receivers the message was sent to. If there are no receivers, the
+    /// message is returned back as an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::broadcast;
+    ///
+    /// let (s, r) = broadcast(1);
+    /// let r2 = s.subscribe();
+    /// assert_eq!(s.send(1), Ok(2));
+    ///
+    /// drop(r);
+    /// drop(r2);
+    /// assert_eq!(s.send(2), Err(2));
+    /// ```
+    pub fn send(&self, msg: T) -> Result<usize, T> {
+        let mut state = self.channel.state();
+        if state.receiver_count == 0 {
+            return Err(msg);
+        }
+
+        // Make room by removing the oldest message, which is dropped outside of the lock.
+        let mut oldest = None;
+        if state.buffer.len() == self.channel.cap {
+            oldest = state.buffer.pop_front();
+            state.head += 1;
+        }
+        state.buffer.push_back(msg);
+
+        let receiver_count = state.receiver_count;
+        drop(state);
+        drop(oldest);
+
+        self.channel.wakers.notify_all();
+        Ok(receiver_count)
+    }
+
+    /// Creates a new receiver that sees the messages sent from now on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::{broadcast, BroadcastTryRecvError};
+    ///
+    /// let (s, _r) = broadcast(2);
+    /// s.send(1).unwrap();
+    ///
+    /// let mut r2 = s.subscribe();
+    /// assert_eq!(r2.try_recv(), Err(BroadcastTryRecvError::Empty));
+    ///
+    /// s.send(2).unwrap();
+    /// assert_eq!(r2.try_recv(), Ok(2));
+    /// ```
+    pub fn subscribe(&self) -> BroadcastReceiver<T> {
+        let mut state = self.channel.state();
+        state.receiver_count += 1;
+        let next = state.tail();
+        drop(state);
+
+        BroadcastReceiver {
+            channel: self.channel.clone(),
+            next,
+        }
+    }
+
+    /// Returns the number of receivers.
+    pub fn receiver_count(&self) -> usize {
+        self.channel.state().receiver_count
+    }
+}
+
+impl<T> Drop for BroadcastSender<T> {
+    fn drop(&mut self) {
+        // Decrement the sender count and close the channel if it drops down to zero.
+        if self.channel.sender_count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.channel.state().closed = true;
+            self.channel.wakers.notify_all();
+        }
+    }
+}
+
+impl<T> Clone for BroadcastSender<T> {
+    fn clone(&self) -> BroadcastSender<T> {
+        let count = self.channel.sender_count.fetch_add(1, Ordering::Relaxed);
+
+        // Make sure the count never overflows, even if lots of sender clones are leaked.
+        if count > isize::MAX as usize {
+            process::abort();
+        }
+
+        BroadcastSender {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<T> Debug for BroadcastSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("BroadcastSender { .. }")
+    }
+}
+
+/// The receiving side of a broadcast channel.
+///
+/// This struct is created by the [`broadcast`] function or by [`BroadcastSender::subscribe`]. See
+/// the documentation of [`broadcast`] for more.
+///
+/// [`broadcast`]: fn.broadcast.html
+/// [`BroadcastSender::subscribe`]: struct.BroadcastSender.html#method.subscribe
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct BroadcastReceiver<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+
+    /// The position of the next message to receive.
+    next: u64,
+}
+
+impl<T: Clone> BroadcastReceiver<T> {
+    /// Receives the next message from the channel.
+    ///
+    /// Returns [`Lagged`] if messages were dropped before this receiver saw them, and [`Closed`]
+    /// if all senders have been dropped and there are no messages left.
+    ///
+    /// [`Lagged`]: enum.BroadcastRecvError.html#variant.Lagged
+    /// [`Closed`]: enum.BroadcastRecvError.html#variant.Closed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{broadcast, BroadcastRecvError};
+    ///
+    /// let (s, mut r) = broadcast(2);
+    /// for i in 0..5 {
+    ///     s.send(i).unwrap();
+    /// }
+    /// drop(s);
+    ///
+    /// assert_eq!(r.recv().await, Err(BroadcastRecvError::Lagged(3)));
+    /// assert_eq!(r.recv().await, Ok(3));
+    /// assert_eq!(r.recv().await, Ok(4));
+    /// assert_eq!(r.recv().await, Err(BroadcastRecvError::Closed));
+    /// #
+    /// # })
+    /// ```
+    pub async fn recv(&mut self) -> Result<T, BroadcastRecvError> {
+        struct RecvFuture<'a, T> {
+            receiver: &'a mut BroadcastReceiver<T>,
+            opt_key: Option<usize>,
+        }
+
+        impl<T: Clone> Future for RecvFuture<'_, T> {
+            type Output = Result<T, BroadcastRecvError>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = &mut *self;
+                loop {
+                    // If the current task is in the set, remove it.
+                    if let Some(key) = this.opt_key.take() {
+                        this.receiver.channel.wakers.remove(key);
+                    }
+
+                    // Try receiving a message.
+                    match this.receiver.try_recv() {
+                        Ok(msg) => return Poll::Ready(Ok(msg)),
+                        Err(BroadcastTryRecvError::Lagged(n)) => {
+                            return Poll::Ready(Err(BroadcastRecvError::Lagged(n)))
+                        }
+                        Err(BroadcastTryRecvError::Closed) => {
+                            return Poll::Ready(Err(BroadcastRecvError::Closed))
+                        }
+                        Err(BroadcastTryRecvError::Empty) => {
+                            // Insert this receive operation.
+                            this.opt_key = Some(this.receiver.channel.wakers.insert(cx));
+
+                            // If there is still no message and the channel is open, return.
+                            let state = this.receiver.channel.state();
+                            if this.receiver.next == state.tail() && !state.closed {
+                                return Poll::Pending;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        impl<T> Drop for RecvFuture<'_, T> {
+            fn drop(&mut self) {
+                // All receivers are notified of new messages, so just remove this one.
+                if let Some(key) = self.opt_key {
+                    self.receiver.channel.wakers.remove(key);
+                }
+            }
+        }
+
+        RecvFuture {
+            receiver: self,
+            opt_key: None,
+        }
+        .await
+    }
+
+    /// Attempts to receive the next message without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::{broadcast, BroadcastTryRecvError};
+    ///
+    /// let (s, mut r) = broadcast(1);
+    /// assert_eq!(r.try_recv(), Err(BroadcastTryRecvError::Empty));
+    ///
+    /// s.send(1).unwrap();
+    /// s.send(2).unwrap();
+    /// assert_eq!(r.try_recv(), Err(BroadcastTryRecvError::Lagged(1)));
+    /// assert_eq!(r.try_recv(), Ok(2));
+    /// ```
+    pub fn try_recv(&mut self) -> Result<T, BroadcastTryRecvError> {
+        let state = self.channel.state();
+
+        if self.next < state.head {
+            // Skip the messages that were dropped before this receiver saw them.
+            let missed = state.head - self.next;
+            self.next = state.head;
+            return Err(BroadcastTryRecvError::Lagged(missed));
+        }
+
+        match state.buffer.get((self.next - state.head) as usize) {
+            Some(msg) => {
+                // Nothing is updated until the message is cloned, in case cloning panics.
+                let msg = msg.clone();
+                self.next += 1;
+                Ok(msg)
+            }
+            None if state.closed => Err(BroadcastTryRecvError::Closed),
+            None => Err(BroadcastTryRecvError::Empty),
+        }
+    }
+}
+
+impl<T> Drop for BroadcastReceiver<T> {
+    fn drop(&mut self) {
+        self.channel.state().receiver_count -= 1;
+    }
+}
+
+impl<T> Clone for BroadcastReceiver<T> {
+    /// Creates a receiver that sees the same messages as this one from now on.
+    fn clone(&self) -> BroadcastReceiver<T> {
+        self.channel.state().receiver_count += 1;
+
+        BroadcastReceiver {
+            channel: self.channel.clone(),
+            next: self.next,
+        }
+    }
+}
+
+impl<T> Debug for BroadcastReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("BroadcastReceiver { .. }")
+    }
+}
+
+/// An error returned from the `recv` method of a [`BroadcastReceiver`].
+///
+/// [`BroadcastReceiver`]: struct.BroadcastReceiver.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BroadcastRecvError {
+    /// The receiver fell behind, and this many messages were dropped before it saw them.
+    ///
+    /// The next receive operation returns the oldest message that is still kept.
+    Lagged(u64),
+
+    /// All senders have been dropped and there are no messages left.
+    Closed,
+}
+
+impl Error for BroadcastRecvError {}
+
+impl Display for BroadcastRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Lagged(n) => write!(f, "The receiver lagged behind by {} messages.", n),
+            Self::Closed => Display::fmt("The channel is empty and closed.", f),
+        }
+    }
+}
+
+/// An error returned from the `try_recv` method of a [`BroadcastReceiver`].
+///
+/// [`BroadcastReceiver`]: struct.BroadcastReceiver.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BroadcastTryRecvError {
+    /// There are no new messages but the channel is not closed.
+    Empty,
+
+    /// The receiver fell behind, and this many messages were dropped before it saw them.
+    ///
+    /// The next receive operation returns the oldest message that is still kept.
+    Lagged(u64),
+
+    /// All senders have been dropped and there are no messages left.
+    Closed,
+}
+
+impl Error for BroadcastTryRecvError {}
+
+impl Display for BroadcastTryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => Display::fmt("The channel is empty.", f),
+            Self::Lagged(n) => write!(f, "The receiver lagged behind by {} messages.", n),
+            Self::Closed => Display::fmt("The channel is empty and closed.", f),
+        }
+    }
+}
+
+/// The state shared by all senders and receivers of a broadcast channel.
+struct Channel<T> {
+    /// The buffered messages and bookkeeping.
+    state: Mutex<State<T>>,
+
+    /// The maximum number of buffered messages.
+    cap: usize,
+
+    /// The number of senders.
+    sender_count: AtomicUsize,
+
+    /// Receive operations waiting for a message.
+    wakers: WakerSet,
+}
+
+impl<T> Channel<T> {
+    /// Locks the state of the channel.
+    ///
+    /// Messages are cloned and dropped only where a panic leaves the state consistent, so a
+    /// poisoned lock is ignored instead of spreading the panic to every sender and receiver, and
+    /// aborting the process when one of them gets dropped while unwinding.
+    fn state(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+unsafe impl<T: Send> Send for Channel<T> {}
+unsafe impl<T: Send> Sync for Channel<T> {}
+
+/// The mutable state of a broadcast channel.
+struct State<T> {
+    /// The last `cap` messages that were sent.
+    buffer: VecDeque<T>,
+
+    /// The position of the first message in the buffer.
+    head: u64,
+
+    /// The number of receivers.
+    receiver_count: usize,
+
+    /// Set when all senders have been dropped.
+    closed: bool,
+}
+
+impl<T> State<T> {
+    /// Returns the position of the next message to be sent.
+    fn tail(&self) -> u64 {
+        self.head + self.buffer.len() as u64
+    }
+}
@@ -1,4 +1,5 @@
 use std::cell::UnsafeCell;
+use std::collections::VecDeque;
 use std::error::Error;
 use std::fmt::{self, Debug, Display};
 use std::future::Future;
@@ -8,8 +9,8 @@ use std::mem;
 use std::pin::Pin;
 use std::process;
 use std::ptr;
-use std::sync::atomic::{self, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{self, AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 
 use crossbeam_utils::Backoff;
@@ -27,7 +28,10 @@ use crate::sync::WakerSet;
 ///
 /// # Panics
 ///
-/// If `cap` is zero, this function will panic.
+/// If `cap` is zero, this function will panic. Use [`unbounded`] for a channel without a limit on
+/// the number of messages.
+///
+/// [`unbounded`]: fn.unbounded.html
 ///
 /// # Examples
 ///
@@ -61,7 +65,56 @@ use crate::sync::WakerSet;
 #[cfg(feature = "unstable")]
 #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
 pub fn channel<T>(cap: usize) -> (Sender<T>, Receiver<T>) {
-    let channel = Arc::new(Channel::with_capacity(cap));
+    let channel = Arc::new(Channel::new(Flavor::Array(Array::with_capacity(cap))));
+    let s = Sender {
+        channel: channel.clone(),
+    };
+    let r = Receiver {
+        channel,
+        opt_key: None,
+    };
+    (s, r)
+}
+
+/// Creates an unbounded multi-producer multi-consumer channel.
+///
+/// This channel has a buffer that can hold any number of messages at a time, so sending into it
+/// never waits for room. It is useful when backpressure is handled elsewhere, but a slow receiver
+/// lets messages pile up in memory.
+///
+/// Apart from that, it works like a channel created by [`channel`], with the same [`Sender`] and
+/// [`Receiver`] types.
+///
+/// [`channel`]: fn.channel.html
+/// [`Sender`]: struct.Sender.html
+/// [`Receiver`]: struct.Receiver.html
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), async_std::sync::RecvError> {
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::unbounded;
+///
+/// let (s, r) = unbounded();
+///
+/// // These calls return immediately, however many messages are in the channel.
+/// for i in 0..1000 {
+///     s.send(i).await;
+/// }
+/// assert_eq!(r.len(), 1000);
+///
+/// assert_eq!(r.recv().await?, 0);
+/// assert_eq!(r.recv().await?, 1);
+/// # Ok(())
+/// #
+/// # }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn unbounded<T>() -> (Sender<T>, Receiver<T>) {
+    let channel = Arc::new(Channel::new(Flavor::List(List::new())));
     let s = Sender {
         channel: channel.clone(),
     };
@@ -74,10 +127,11 @@ pub fn channel<T>(cap: usize) -> (Sender<T>, Receiver<T>) {
 
 /// The sending side of a channel.
 ///
-/// This struct is created by the [`channel`] function. See its
-/// documentation for more.
+/// This struct is created by the [`channel`] and [`unbounded`] functions. See
+/// their documentation for more.
 ///
 /// [`channel`]: fn.channel.html
+/// [`unbounded`]: fn.unbounded.html
 ///
 /// # Examples
 ///
@@ -111,6 +165,7 @@ impl<T> Sender<T> {
     /// Sends a message into the channel.
     ///
     /// If the channel is full, this method will wait until there is space in the channel.
+    /// Unbounded channels are never full, so sending into them never waits for space.
     ///
     /// # Examples
     ///
@@ -220,6 +275,8 @@ impl<T> Sender<T> {
 
     /// Returns the channel capacity.
     ///
+    /// The capacity of an unbounded channel is `usize::MAX`.
+    ///
     /// # Examples
     ///
     /// ```
@@ -229,7 +286,7 @@ impl<T> Sender<T> {
     /// assert_eq!(s.capacity(), 5);
     /// ```
     pub fn capacity(&self) -> usize {
-        self.channel.cap
+        self.channel.capacity()
     }
 
     /// Returns `true` if the channel is empty.
@@ -295,6 +352,23 @@ impl<T> Sender<T> {
     pub fn len(&self) -> usize {
         self.channel.len()
     }
+
+    /// Returns `true` if all receivers of the channel have been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::channel;
+    ///
+    /// let (s, r) = channel::<i32>(1);
+    ///
+    /// assert!(!s.is_disconnected());
+    /// drop(r);
+    /// assert!(s.is_disconnected());
+    /// ```
+    pub fn is_disconnected(&self) -> bool {
+        self.channel.is_disconnected()
+    }
 }
 
 impl<T> Drop for Sender<T> {
@@ -331,9 +405,10 @@ impl<T> fmt::Debug for Sender<T> {
 ///
 /// This type receives messages by calling `recv`. But it also implements the [`Stream`] trait,
 /// which means it can act as an asynchronous iterator. This struct is created by the [`channel`]
-/// function. See its documentation for more.
+/// and [`unbounded`] functions. See their documentation for more.
 ///
 /// [`channel`]: fn.channel.html
+/// [`unbounded`]: fn.unbounded.html
 /// [`Stream`]: ../stream/trait.Stream.html
 ///
 /// # Examples
@@ -413,7 +488,7 @@ impl<T> Receiver<T> {
 
             fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
                 poll_recv(
-                    &self.channel,
+                    self.channel,
                     &self.channel.recv_wakers,
                     &mut self.opt_key,
                     cx,
@@ -463,6 +538,8 @@ impl<T> Receiver<T> {
 
     /// Returns the channel capacity.
     ///
+    /// The capacity of an unbounded channel is `usize::MAX`.
+    ///
     /// # Examples
     ///
     /// ```
@@ -472,7 +549,7 @@ impl<T> Receiver<T> {
     /// assert_eq!(r.capacity(), 5);
     /// ```
     pub fn capacity(&self) -> usize {
-        self.channel.cap
+        self.channel.capacity()
     }
 
     /// Returns `true` if the channel is empty.
@@ -538,6 +615,23 @@ impl<T> Receiver<T> {
     pub fn len(&self) -> usize {
         self.channel.len()
     }
+
+    /// Returns `true` if all senders of the channel have been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::channel;
+    ///
+    /// let (s, r) = channel::<i32>(1);
+    ///
+    /// assert!(!r.is_disconnected());
+    /// drop(s);
+    /// assert!(r.is_disconnected());
+    /// ```
+    pub fn is_disconnected(&self) -> bool {
+        self.channel.is_disconnected()
+    }
 }
 
 impl<T> Drop for Receiver<T> {
@@ -633,8 +727,140 @@ struct Slot<T> {
     msg: UnsafeCell<T>,
 }
 
-/// Bounded channel based on a preallocated array.
+/// The state shared by the senders and receivers of a channel.
 struct Channel<T> {
+    /// The queue holding the messages.
+    flavor: Flavor<T>,
+
+    /// Send operations waiting while the channel is full.
+    send_wakers: WakerSet,
+
+    /// Receive operations waiting while the channel is empty and not disconnected.
+    recv_wakers: WakerSet,
+
+    /// Streams waiting while the channel is empty and not disconnected.
+    stream_wakers: WakerSet,
+
+    /// The number of currently active `Sender`s.
+    sender_count: AtomicUsize,
+
+    /// The number of currently active `Receivers`s.
+    receiver_count: AtomicUsize,
+}
+
+unsafe impl<T: Send> Send for Channel<T> {}
+unsafe impl<T: Send> Sync for Channel<T> {}
+impl<T> Unpin for Channel<T> {}
+
+/// The queue of a channel.
+enum Flavor<T> {
+    /// A bounded queue, created by `channel`.
+    Array(Array<T>),
+
+    /// An unbounded queue, created by `unbounded`.
+    List(List<T>),
+}
+
+impl<T> Channel<T> {
+    /// Creates a channel around the given queue.
+    fn new(flavor: Flavor<T>) -> Self {
+        Channel {
+            flavor,
+            send_wakers: WakerSet::new(),
+            recv_wakers: WakerSet::new(),
+            stream_wakers: WakerSet::new(),
+            sender_count: AtomicUsize::new(1),
+            receiver_count: AtomicUsize::new(1),
+        }
+    }
+
+    /// Attempts to send a message.
+    fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        match &self.flavor {
+            Flavor::Array(array) => array.try_send(msg)?,
+            Flavor::List(list) => list.try_send(msg)?,
+        }
+
+        // Wake a blocked receive operation.
+        self.recv_wakers.notify_one();
+
+        // Wake all blocked streams.
+        self.stream_wakers.notify_all();
+
+        Ok(())
+    }
+
+    /// Attempts to receive a message.
+    fn try_recv(&self) -> Result<T, TryRecvError> {
+        let msg = match &self.flavor {
+            Flavor::Array(array) => array.try_recv()?,
+            Flavor::List(list) => list.try_recv()?,
+        };
+
+        // Wake a blocked send operation.
+        self.send_wakers.notify_one();
+
+        Ok(msg)
+    }
+
+    /// Returns the channel capacity, which is `usize::MAX` for unbounded channels.
+    fn capacity(&self) -> usize {
+        match &self.flavor {
+            Flavor::Array(array) => array.cap,
+            Flavor::List(_) => usize::MAX,
+        }
+    }
+
+    /// Returns the current number of messages inside the channel.
+    fn len(&self) -> usize {
+        match &self.flavor {
+            Flavor::Array(array) => array.len(),
+            Flavor::List(list) => list.len(),
+        }
+    }
+
+    /// Returns `true` if the channel is disconnected.
+    fn is_disconnected(&self) -> bool {
+        match &self.flavor {
+            Flavor::Array(array) => array.is_disconnected(),
+            Flavor::List(list) => list.is_disconnected(),
+        }
+    }
+
+    /// Returns `true` if the channel is empty.
+    fn is_empty(&self) -> bool {
+        match &self.flavor {
+            Flavor::Array(array) => array.is_empty(),
+            Flavor::List(list) => list.len() == 0,
+        }
+    }
+
+    /// Returns `true` if the channel is full.
+    fn is_full(&self) -> bool {
+        match &self.flavor {
+            Flavor::Array(array) => array.is_full(),
+            Flavor::List(_) => false,
+        }
+    }
+
+    /// Disconnects the channel and wakes up all blocked operations.
+    fn disconnect(&self) {
+        let disconnected = match &self.flavor {
+            Flavor::Array(array) => array.disconnect(),
+            Flavor::List(list) => list.disconnect(),
+        };
+
+        if disconnected {
+            // Notify everyone blocked on this channel.
+            self.send_wakers.notify_all();
+            self.recv_wakers.notify_all();
+            self.stream_wakers.notify_all();
+        }
+    }
+}
+
+/// Bounded queue based on a preallocated array.
+struct Array<T> {
     /// The head of the channel.
     ///
     /// This value is a "stamp" consisting of an index into the buffer, a mark bit, and a lap, but
@@ -666,31 +892,12 @@ struct Channel<T> {
     /// were dropped.
     mark_bit: usize,
 
-    /// Send operations waiting while the channel is full.
-    send_wakers: WakerSet,
-
-    /// Receive operations waiting while the channel is empty and not disconnected.
-    recv_wakers: WakerSet,
-
-    /// Streams waiting while the channel is empty and not disconnected.
-    stream_wakers: WakerSet,
-
-    /// The number of currently active `Sender`s.
-    sender_count: AtomicUsize,
-
-    /// The number of currently active `Receivers`s.
-    receiver_count: AtomicUsize,
-
-    /// Indicates that dropping a `Channel<T>` may drop values of type `T`.
+    /// Indicates that dropping an `Array<T>` may drop values of type `T`.
     _marker: PhantomData<T>,
 }
 
-unsafe impl<T: Send> Send for Channel<T> {}
-unsafe impl<T: Send> Sync for Channel<T> {}
-impl<T> Unpin for Channel<T> {}
-
-impl<T> Channel<T> {
-    /// Creates a bounded channel of capacity `cap`.
+impl<T> Array<T> {
+    /// Creates a bounded queue of capacity `cap`.
     fn with_capacity(cap: usize) -> Self {
         assert!(cap > 0, "capacity must be positive");
 
@@ -720,18 +927,13 @@ impl<T> Channel<T> {
             }
         }
 
-        Channel {
+        Array {
             buffer,
             cap,
             one_lap,
             mark_bit,
             head: AtomicUsize::new(head),
             tail: AtomicUsize::new(tail),
-            send_wakers: WakerSet::new(),
-            recv_wakers: WakerSet::new(),
-            stream_wakers: WakerSet::new(),
-            sender_count: AtomicUsize::new(1),
-            receiver_count: AtomicUsize::new(1),
             _marker: PhantomData,
         }
     }
@@ -783,12 +985,6 @@ impl<T> Channel<T> {
                         let stamp = tail + 1;
                         slot.stamp.store(stamp, Ordering::Release);
 
-                        // Wake a blocked receive operation.
-                        self.recv_wakers.notify_one();
-
-                        // Wake all blocked streams.
-                        self.stream_wakers.notify_all();
-
                         return Ok(());
                     }
                     Err(t) => {
@@ -861,9 +1057,6 @@ impl<T> Channel<T> {
                         let stamp = head.wrapping_add(self.one_lap);
                         slot.stamp.store(stamp, Ordering::Release);
 
-                        // Wake a blocked send operation.
-                        self.send_wakers.notify_one();
-
                         return Ok(msg);
                     }
                     Err(h) => {
@@ -922,7 +1115,7 @@ impl<T> Channel<T> {
     }
 
     /// Returns `true` if the channel is disconnected.
-    pub fn is_disconnected(&self) -> bool {
+    fn is_disconnected(&self) -> bool {
         self.tail.load(Ordering::SeqCst) & self.mark_bit != 0
     }
 
@@ -950,20 +1143,16 @@ impl<T> Channel<T> {
         head.wrapping_add(self.one_lap) == tail & !self.mark_bit
     }
 
-    /// Disconnects the channel and wakes up all blocked operations.
-    fn disconnect(&self) {
+    /// Disconnects the channel.
+    ///
+    /// Returns `true` if this call disconnected the channel.
+    fn disconnect(&self) -> bool {
         let tail = self.tail.fetch_or(self.mark_bit, Ordering::SeqCst);
-
-        if tail & self.mark_bit == 0 {
-            // Notify everyone blocked on this channel.
-            self.send_wakers.notify_all();
-            self.recv_wakers.notify_all();
-            self.stream_wakers.notify_all();
-        }
+        tail & self.mark_bit == 0
     }
 }
 
-impl<T> Drop for Channel<T> {
+impl<T> Drop for Array<T> {
     fn drop(&mut self) {
         // Get the index of the head.
         let hix = self.head.load(Ordering::Relaxed) & (self.mark_bit - 1);
@@ -989,6 +1178,73 @@ impl<T> Drop for Channel<T> {
     }
 }
 
+/// Unbounded queue based on a growable ring buffer.
+struct List<T> {
+    /// The messages in the channel.
+    queue: Mutex<VecDeque<T>>,
+
+    /// Set when either all senders were dropped or all receivers were dropped.
+    disconnected: AtomicBool,
+}
+
+impl<T> List<T> {
+    /// Creates an empty unbounded queue.
+    fn new() -> Self {
+        List {
+            queue: Mutex::new(VecDeque::new()),
+            disconnected: AtomicBool::new(false),
+        }
+    }
+
+    /// Attempts to send a message, which only fails if all receivers have been dropped.
+    fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        let mut queue = self.queue.lock().unwrap();
+
+        // If all receivers have been dropped, nobody will ever receive the message, so give it
+        // back instead of keeping it until the channel is dropped.
+        if self.disconnected.load(Ordering::SeqCst) {
+            return Err(TrySendError::Disconnected(msg));
+        }
+
+        queue.push_back(msg);
+        Ok(())
+    }
+
+    /// Attempts to receive a message.
+    fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut queue = self.queue.lock().unwrap();
+
+        if let Some(msg) = queue.pop_front() {
+            return Ok(msg);
+        }
+
+        // Check the flag while holding the lock, so that a message sent before the last sender
+        // was dropped can't be missed.
+        if self.disconnected.load(Ordering::SeqCst) {
+            Err(TryRecvError::Disconnected)
+        } else {
+            Err(TryRecvError::Empty)
+        }
+    }
+
+    /// Returns the current number of messages inside the channel.
+    fn len(&self) -> usize {
+        self.queue.lock().unwrap().len()
+    }
+
+    /// Returns `true` if the channel is disconnected.
+    fn is_disconnected(&self) -> bool {
+        self.disconnected.load(Ordering::SeqCst)
+    }
+
+    /// Disconnects the channel.
+    ///
+    /// Returns `true` if this call disconnected the channel.
+    fn disconnect(&self) -> bool {
+        !self.disconnected.swap(true, Ordering::SeqCst)
+    }
+}
+
 /// An error returned from the `try_send` method.
 #[cfg(feature = "unstable")]
 #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
@@ -129,30 +129,49 @@
 //!   to reach a point in the program, before continuing execution all
 //!   together.
 //!
+//! - [`broadcast`]: Multi-producer, multi-consumer channels where
+//!   every receiver sees every message, used for fanning out events.
+//!
 //! - [`channel`]: Multi-producer, multi-consumer queues, used for
 //!   message-based communication. Can provide a lightweight
 //!   inter-task synchronisation mechanism, at the cost of some
-//!   extra memory.
+//!   extra memory. Use [`unbounded`] for a queue without a capacity
+//!   limit.
 //!
 //! - [`Mutex`]: Mutual exclusion mechanism, which ensures that at
 //!   most one task at a time is able to access some data.
 //!
+//! - [`oneshot`]: A channel for sending a single value, such as the
+//!   response to a request.
+//!
 //! - [`RwLock`]: Provides a mutual exclusion mechanism which allows
 //!   multiple readers at the same time, while allowing only one
 //!   writer at a time. In some cases, this can be more efficient than
 //!   a mutex.
 //!
+//! - [`Semaphore`]: Holds a number of permits, which limits how many
+//!   tasks can do some work at the same time.
+//!
+//! - [`watch`]: A channel holding a single value, which receivers can
+//!   borrow and wait for changes of, such as configuration.
+//!
 //! [`Arc`]: struct.Arc.html
 //! [`Barrier`]: struct.Barrier.html
+//! [`broadcast`]: fn.broadcast.html
 //! [`channel`]: fn.channel.html
 //! [`Mutex`]: struct.Mutex.html
+//! [`oneshot`]: fn.oneshot.html
 //! [`RwLock`]: struct.RwLock.html
+//! [`Semaphore`]: struct.Semaphore.html
+//! [`unbounded`]: fn.unbounded.html
+//! [`watch`]: fn.watch.html
 //!
 //! # Examples
 //!
 //! Spawn a task that updates an integer protected by a mutex:
 //!
 //! ```
+//! # #![allow(unused_must_use)]
 //! # async_std::task::block_on(async {
 //! #
 //! use async_std::sync::{Arc, Mutex};
@@ -184,12 +203,24 @@ mod rwlock;
 
 cfg_unstable! {
     pub use barrier::{Barrier, BarrierWaitResult};
-    pub use channel::{channel, Sender, Receiver, RecvError, TryRecvError, TrySendError};
+    pub use broadcast::{
+        broadcast, BroadcastReceiver, BroadcastRecvError, BroadcastSender, BroadcastTryRecvError,
+    };
+    pub use channel::{channel, unbounded, Sender, Receiver, RecvError, TryRecvError, TrySendError};
     pub use condvar::Condvar;
+    pub use oneshot::{oneshot, Canceled, OneshotReceiver, OneshotSender};
+    pub use semaphore::{
+        AcquireError, OwnedSemaphorePermit, Semaphore, SemaphorePermit, TryAcquireError,
+    };
+    pub use watch::{watch, WatchReceiver, WatchRecvError, WatchRef, WatchSender};
 
     mod barrier;
+    mod broadcast;
     mod condvar;
     mod channel;
+    mod oneshot;
+    mod semaphore;
+    mod watch;
 }
 
 pub(crate) mod waker_set;
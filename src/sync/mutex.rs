@@ -17,6 +17,7 @@ use crate::task::{Context, Poll};
 /// # Examples
 ///
 /// ```
+/// # #![allow(unused_must_use)]
 /// # async_std::task::block_on(async {
 /// #
 /// use async_std::sync::{Arc, Mutex};
@@ -75,6 +76,7 @@ impl<T: ?Sized> Mutex<T> {
     /// # Examples
     ///
     /// ```
+    /// # #![allow(unused_must_use)]
     /// # async_std::task::block_on(async {
     /// #
     /// use async_std::sync::{Arc, Mutex};
@@ -151,6 +153,7 @@ impl<T: ?Sized> Mutex<T> {
     /// # Examples
     ///
     /// ```
+    /// # #![allow(unused_must_use)]
     /// # async_std::task::block_on(async {
     /// #
     /// use async_std::sync::{Arc, Mutex};
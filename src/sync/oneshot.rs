@@ -0,0 +1,371 @@
+use std::cell::UnsafeCell;
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::sync::{TryRecvError, WakerSet};
+use crate::task::{Context, Poll};
+
+/// Set when the value has been sent.
+#[allow(clippy::identity_op)]
+const SENT: usize = 1 << 0;
+
+/// Set when the sender has been dropped.
+const SENDER_DROPPED: usize = 1 << 1;
+
+/// Set when the receiver has been dropped.
+const RECEIVER_DROPPED: usize = 1 << 2;
+
+/// Creates a channel for sending a single value.
+///
+/// The [`OneshotSender`] is consumed when the value is sent, and the [`OneshotReceiver`] is a
+/// future that resolves to the value. If the sender is dropped without sending a value, the
+/// receiver resolves to a [`Canceled`] error instead.
+///
+/// [`OneshotSender`]: struct.OneshotSender.html
+/// [`OneshotReceiver`]: struct.OneshotReceiver.html
+/// [`Canceled`]: struct.Canceled.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::oneshot;
+/// use async_std::task;
+///
+/// let (s, r) = oneshot();
+///
+/// task::spawn(async move {
+///     s.send(1 + 2).unwrap();
+/// });
+///
+/// assert_eq!(r.await, Ok(3));
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn oneshot<T>() -> (OneshotSender<T>, OneshotReceiver<T>) {
+    let channel = Arc::new(Channel {
+        state: AtomicUsize::new(0),
+        value: UnsafeCell::new(None),
+        recv_wakers: WakerSet::new(),
+        closed_wakers: WakerSet::new(),
+    });
+    let s = OneshotSender {
+        channel: channel.clone(),
+    };
+    let r = OneshotReceiver {
+        channel,
+        opt_key: None,
+    };
+    (s, r)
+}
+
+/// The sending side of a oneshot channel.
+///
+/// This struct is created by the [`oneshot`] function. See its documentation for more.
+///
+/// [`oneshot`]: fn.oneshot.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct OneshotSender<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> OneshotSender<T> {
+    /// Sends the value, consuming the sender.
+    ///
+    /// If the receiver has been dropped, the value is returned back as an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::oneshot;
+    ///
+    /// let (s, r) = oneshot();
+    /// drop(r);
+    /// assert_eq!(s.send(1), Err(1));
+    /// ```
+    pub fn send(self, value: T) -> Result<(), T> {
+        if self.is_closed() {
+            return Err(value);
+        }
+
+        // Only the sender writes the value, and the receiver only reads it once `SENT` is set.
+        unsafe { *self.channel.value.get() = Some(value) };
+
+        let state = self.channel.state.fetch_or(SENT, Ordering::SeqCst);
+        if state & RECEIVER_DROPPED != 0 {
+            // The receiver went away in the meantime and will never read the value.
+            let value = unsafe { (*self.channel.value.get()).take() };
+            return Err(value.unwrap());
+        }
+
+        self.channel.recv_wakers.notify_all();
+        Ok(())
+    }
+
+    /// Returns `true` if the receiver has been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::oneshot;
+    ///
+    /// let (s, r) = oneshot::<i32>();
+    /// assert!(!s.is_closed());
+    /// drop(r);
+    /// assert!(s.is_closed());
+    /// ```
+    pub fn is_closed(&self) -> bool {
+        self.channel.state.load(Ordering::SeqCst) & RECEIVER_DROPPED != 0
+    }
+
+    /// Waits until the receiver is dropped.
+    ///
+    /// This is useful to stop computing a value that nobody is interested in anymore.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::oneshot;
+    /// use async_std::task;
+    ///
+    /// let (s, r) = oneshot::<i32>();
+    ///
+    /// let handle = task::spawn(async move {
+    ///     s.closed().await;
+    ///     println!("the receiver went away");
+    /// });
+    ///
+    /// drop(r);
+    /// handle.await.unwrap();
+    /// #
+    /// # })
+    /// ```
+    pub async fn closed(&self) {
+        struct ClosedFuture<'a, T> {
+            sender: &'a OneshotSender<T>,
+            opt_key: Option<usize>,
+        }
+
+        impl<T> Future for ClosedFuture<'_, T> {
+            type Output = ();
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+                let wakers = &self.sender.channel.closed_wakers;
+                loop {
+                    // If the current task is in the set, remove it.
+                    if let Some(key) = self.opt_key.take() {
+                        wakers.remove(key);
+                    }
+
+                    if self.sender.is_closed() {
+                        return Poll::Ready(());
+                    }
+
+                    // Insert this operation.
+                    self.opt_key = Some(wakers.insert(cx));
+
+                    // If the receiver is still alive, return.
+                    if !self.sender.is_closed() {
+                        return Poll::Pending;
+                    }
+                }
+            }
+        }
+
+        impl<T> Drop for ClosedFuture<'_, T> {
+            fn drop(&mut self) {
+                if let Some(key) = self.opt_key {
+                    self.sender.channel.closed_wakers.remove(key);
+                }
+            }
+        }
+
+        ClosedFuture {
+            sender: self,
+            opt_key: None,
+        }
+        .await
+    }
+}
+
+impl<T> Drop for OneshotSender<T> {
+    fn drop(&mut self) {
+        // This is a no-op for the receiver if the value has been sent.
+        self.channel
+            .state
+            .fetch_or(SENDER_DROPPED, Ordering::SeqCst);
+        self.channel.recv_wakers.notify_all();
+    }
+}
+
+impl<T> Debug for OneshotSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("OneshotSender { .. }")
+    }
+}
+
+/// The receiving side of a oneshot channel.
+///
+/// This type is a future that resolves to the sent value, or to a [`Canceled`] error if the
+/// sender is dropped without sending one. This struct is created by the [`oneshot`] function. See
+/// its documentation for more.
+///
+/// [`Canceled`]: struct.Canceled.html
+/// [`oneshot`]: fn.oneshot.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::{oneshot, Canceled};
+///
+/// let (s, r) = oneshot::<i32>();
+/// drop(s);
+/// assert_eq!(r.await, Err(Canceled));
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct OneshotReceiver<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+
+    /// The key for this receiver in the `channel.recv_wakers` set.
+    opt_key: Option<usize>,
+}
+
+impl<T> OneshotReceiver<T> {
+    /// Attempts to receive the value without waiting.
+    ///
+    /// Returns [`TryRecvError::Empty`] if the value hasn't been sent yet, and
+    /// [`TryRecvError::Disconnected`] if the sender has been dropped without sending it, or if it
+    /// has already been received.
+    ///
+    /// [`TryRecvError::Empty`]: enum.TryRecvError.html#variant.Empty
+    /// [`TryRecvError::Disconnected`]: enum.TryRecvError.html#variant.Disconnected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::{oneshot, TryRecvError};
+    ///
+    /// let (s, mut r) = oneshot();
+    /// assert_eq!(r.try_recv(), Err(TryRecvError::Empty));
+    ///
+    /// s.send(1).unwrap();
+    /// assert_eq!(r.try_recv(), Ok(1));
+    /// assert_eq!(r.try_recv(), Err(TryRecvError::Disconnected));
+    /// ```
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let state = self.channel.state.load(Ordering::SeqCst);
+
+        if state & SENT != 0 {
+            // Only this receiver takes the value, after the sender has written it.
+            let value = unsafe { (*self.channel.value.get()).take() };
+            value.ok_or(TryRecvError::Disconnected)
+        } else if state & SENDER_DROPPED != 0 {
+            Err(TryRecvError::Disconnected)
+        } else {
+            Err(TryRecvError::Empty)
+        }
+    }
+}
+
+impl<T> Future for OneshotReceiver<T> {
+    type Output = Result<T, Canceled>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        loop {
+            // If the current task is in the set, remove it.
+            if let Some(key) = this.opt_key.take() {
+                this.channel.recv_wakers.remove(key);
+            }
+
+            // Try receiving the value.
+            match this.try_recv() {
+                Ok(value) => return Poll::Ready(Ok(value)),
+                Err(TryRecvError::Disconnected) => return Poll::Ready(Err(Canceled)),
+                Err(TryRecvError::Empty) => {
+                    // Insert this receive operation.
+                    this.opt_key = Some(this.channel.recv_wakers.insert(cx));
+
+                    // If the value is still not sent and the sender is alive, return.
+                    let state = this.channel.state.load(Ordering::SeqCst);
+                    if state & (SENT | SENDER_DROPPED) == 0 {
+                        return Poll::Pending;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T> Drop for OneshotReceiver<T> {
+    fn drop(&mut self) {
+        // If the current task is still in the set, remove it.
+        if let Some(key) = self.opt_key {
+            self.channel.recv_wakers.remove(key);
+        }
+
+        // A value that was sent is dropped along with the channel.
+        self.channel
+            .state
+            .fetch_or(RECEIVER_DROPPED, Ordering::SeqCst);
+        self.channel.closed_wakers.notify_all();
+    }
+}
+
+impl<T> Debug for OneshotReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("OneshotReceiver { .. }")
+    }
+}
+
+/// The error returned by a [`OneshotReceiver`] when the sender is dropped without sending a
+/// value.
+///
+/// [`OneshotReceiver`]: struct.OneshotReceiver.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Canceled;
+
+impl Error for Canceled {}
+
+impl Display for Canceled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt("The sender was dropped without sending a value.", f)
+    }
+}
+
+/// The state shared by both sides of a oneshot channel.
+struct Channel<T> {
+    /// Holds the `SENT`, `SENDER_DROPPED` and `RECEIVER_DROPPED` bits.
+    state: AtomicUsize,
+
+    /// The sent value, until it is received.
+    value: UnsafeCell<Option<T>>,
+
+    /// Receive operations waiting for the value.
+    recv_wakers: WakerSet,
+
+    /// Operations waiting for the receiver to be dropped.
+    closed_wakers: WakerSet,
+}
+
+unsafe impl<T: Send> Send for Channel<T> {}
+unsafe impl<T: Send> Sync for Channel<T> {}
@@ -0,0 +1,561 @@
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use slab::Slab;
+
+use crate::task::{Context, Poll, Waker};
+
+/// A counting semaphore.
+///
+/// A semaphore holds a number of permits. Tasks acquire permits before doing some work and give
+/// them back when they are done, which limits how many tasks can do that work concurrently.
+/// Acquiring waits until enough permits are available.
+///
+/// Permits are given back when the [`SemaphorePermit`] or [`OwnedSemaphorePermit`] that holds them
+/// is dropped. The owned variant keeps the semaphore alive through an [`Arc`], so that it can be
+/// moved into a spawned task.
+///
+/// When permits are given back, only the waiters that the available permits can satisfy are
+/// woken up. Permits are not handed out in the order in which they were requested, and acquiring
+/// doesn't wait behind other waiters. A task that acquires many permits at once can therefore keep
+/// waiting while tasks that acquire fewer permits take them first.
+///
+/// [`SemaphorePermit`]: struct.SemaphorePermit.html
+/// [`OwnedSemaphorePermit`]: struct.OwnedSemaphorePermit.html
+/// [`Arc`]: struct.Arc.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::{Arc, Semaphore};
+/// use async_std::task;
+///
+/// // Allow at most 3 downloads at the same time.
+/// let semaphore = Arc::new(Semaphore::new(3));
+/// let mut handles = Vec::new();
+///
+/// for i in 0..10 {
+///     let permit = semaphore.clone().acquire_owned().await.unwrap();
+///     handles.push(task::spawn(async move {
+///         println!("downloading file {}", i);
+///         drop(permit);
+///     }));
+/// }
+///
+/// for handle in handles {
+///     handle.await.unwrap();
+/// }
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Semaphore {
+    permits: AtomicUsize,
+    closed: AtomicBool,
+    waiters: Mutex<Slab<Waiter>>,
+}
+
+/// An acquire operation waiting for permits.
+struct Waiter {
+    /// The number of permits the operation needs.
+    permits: usize,
+
+    /// The waker of the operation, or `None` if it has been woken up and hasn't acquired its
+    /// permits yet.
+    waker: Option<Waker>,
+}
+
+unsafe impl Send for Semaphore {}
+unsafe impl Sync for Semaphore {}
+
+impl Semaphore {
+    /// Creates a new semaphore with the given number of permits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(10);
+    /// ```
+    pub fn new(permits: usize) -> Semaphore {
+        Semaphore {
+            permits: AtomicUsize::new(permits),
+            closed: AtomicBool::new(false),
+            waiters: Mutex::new(Slab::new()),
+        }
+    }
+
+    /// Returns the number of permits that are currently available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(3);
+    /// let _permit = semaphore.acquire().await.unwrap();
+    /// assert_eq!(semaphore.available_permits(), 2);
+    /// #
+    /// # })
+    /// ```
+    pub fn available_permits(&self) -> usize {
+        self.permits.load(Ordering::SeqCst)
+    }
+
+    /// Adds permits to the semaphore.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the number of available permits would overflow `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(0);
+    /// semaphore.add_permits(2);
+    /// assert_eq!(semaphore.available_permits(), 2);
+    /// ```
+    pub fn add_permits(&self, n: usize) {
+        if n == 0 {
+            return;
+        }
+
+        let mut permits = self.permits.load(Ordering::SeqCst);
+        loop {
+            let new = match permits.checked_add(n) {
+                Some(new) => new,
+                None => panic!("cannot add {} permits to a semaphore with {}", n, permits),
+            };
+
+            match self.permits.compare_exchange_weak(
+                permits,
+                new,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => break,
+                Err(p) => permits = p,
+            }
+        }
+
+        self.notify(&mut self.waiters());
+    }
+
+    /// Acquires a permit.
+    ///
+    /// Returns an error if the semaphore is closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(1);
+    ///
+    /// let permit = semaphore.acquire().await.unwrap();
+    /// assert!(semaphore.try_acquire().is_err());
+    ///
+    /// drop(permit);
+    /// assert!(semaphore.try_acquire().is_ok());
+    /// #
+    /// # })
+    /// ```
+    pub async fn acquire(&self) -> Result<SemaphorePermit<'_>, AcquireError> {
+        self.acquire_many(1).await
+    }
+
+    /// Acquires `n` permits at once.
+    ///
+    /// Returns an error if the semaphore is closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(5);
+    ///
+    /// let permit = semaphore.acquire_many(3).await.unwrap();
+    /// assert_eq!(permit.permits(), 3);
+    /// assert_eq!(semaphore.available_permits(), 2);
+    /// #
+    /// # })
+    /// ```
+    pub async fn acquire_many(&self, n: usize) -> Result<SemaphorePermit<'_>, AcquireError> {
+        pub struct AcquireFuture<'a> {
+            semaphore: &'a Semaphore,
+            n: usize,
+            opt_key: Option<usize>,
+        }
+
+        impl<'a> Future for AcquireFuture<'a> {
+            type Output = Result<SemaphorePermit<'a>, AcquireError>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let semaphore = self.semaphore;
+
+                // Hold the lock so that permits given back in the meantime wake this operation.
+                let mut waiters = semaphore.waiters();
+
+                match semaphore.try_acquire_many(self.n) {
+                    Ok(permit) => {
+                        if let Some(key) = self.opt_key.take() {
+                            waiters.remove(key);
+                        }
+                        Poll::Ready(Ok(permit))
+                    }
+                    Err(TryAcquireError::Closed) => {
+                        if let Some(key) = self.opt_key.take() {
+                            waiters.remove(key);
+                        }
+                        Poll::Ready(Err(AcquireError))
+                    }
+                    Err(TryAcquireError::NoPermits) => {
+                        let waker = Some(cx.waker().clone());
+                        match self.opt_key {
+                            None => {
+                                let permits = self.n;
+                                self.opt_key = Some(waiters.insert(Waiter { permits, waker }));
+                            }
+                            Some(key) => {
+                                // Another operation took the permits this one was woken up for,
+                                // so the remaining permits may satisfy someone else.
+                                if mem::replace(&mut waiters[key].waker, waker).is_none() {
+                                    semaphore.notify(&mut waiters);
+                                }
+                            }
+                        }
+                        Poll::Pending
+                    }
+                }
+            }
+        }
+
+        impl Drop for AcquireFuture<'_> {
+            fn drop(&mut self) {
+                if let Some(key) = self.opt_key {
+                    let mut waiters = self.semaphore.waiters();
+
+                    // Pass the permits this operation was woken up for on to other waiters.
+                    if waiters.remove(key).waker.is_none() {
+                        self.semaphore.notify(&mut waiters);
+                    }
+                }
+            }
+        }
+
+        AcquireFuture {
+            semaphore: self,
+            n,
+            opt_key: None,
+        }
+        .await
+    }
+
+    /// Acquires a permit that keeps the semaphore alive.
+    ///
+    /// Returns an error if the semaphore is closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{Arc, Semaphore};
+    /// use async_std::task;
+    ///
+    /// let semaphore = Arc::new(Semaphore::new(1));
+    /// let permit = semaphore.clone().acquire_owned().await.unwrap();
+    ///
+    /// task::spawn(async move {
+    ///     // The permit is given back when the task completes.
+    ///     drop(permit);
+    /// })
+    /// .await
+    /// .unwrap();
+    ///
+    /// assert_eq!(semaphore.available_permits(), 1);
+    /// #
+    /// # })
+    /// ```
+    pub async fn acquire_owned(self: Arc<Self>) -> Result<OwnedSemaphorePermit, AcquireError> {
+        self.acquire_many_owned(1).await
+    }
+
+    /// Acquires `n` permits at once, keeping the semaphore alive.
+    ///
+    /// Returns an error if the semaphore is closed.
+    pub async fn acquire_many_owned(
+        self: Arc<Self>,
+        n: usize,
+    ) -> Result<OwnedSemaphorePermit, AcquireError> {
+        self.acquire_many(n).await?.forget();
+        Ok(OwnedSemaphorePermit {
+            semaphore: self,
+            permits: n,
+        })
+    }
+
+    /// Attempts to acquire a permit without waiting.
+    ///
+    /// Returns an error if there are no permits available or the semaphore is closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::{Semaphore, TryAcquireError};
+    ///
+    /// let semaphore = Semaphore::new(1);
+    ///
+    /// let permit = semaphore.try_acquire().unwrap();
+    /// assert_eq!(semaphore.try_acquire().unwrap_err(), TryAcquireError::NoPermits);
+    /// ```
+    pub fn try_acquire(&self) -> Result<SemaphorePermit<'_>, TryAcquireError> {
+        self.try_acquire_many(1)
+    }
+
+    /// Attempts to acquire `n` permits at once without waiting.
+    ///
+    /// Returns an error if there are not enough permits available or the semaphore is closed.
+    pub fn try_acquire_many(&self, n: usize) -> Result<SemaphorePermit<'_>, TryAcquireError> {
+        self.take(n)?;
+        Ok(SemaphorePermit {
+            semaphore: self,
+            permits: n,
+        })
+    }
+
+    /// Attempts to acquire a permit that keeps the semaphore alive without waiting.
+    ///
+    /// Returns an error if there are no permits available or the semaphore is closed.
+    pub fn try_acquire_owned(self: Arc<Self>) -> Result<OwnedSemaphorePermit, TryAcquireError> {
+        self.take(1)?;
+        Ok(OwnedSemaphorePermit {
+            semaphore: self,
+            permits: 1,
+        })
+    }
+
+    /// Closes the semaphore.
+    ///
+    /// All pending and future attempts to acquire permits fail. Permits that are already held stay
+    /// valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{Semaphore, TryAcquireError};
+    ///
+    /// let semaphore = Semaphore::new(1);
+    /// semaphore.close();
+    ///
+    /// assert!(semaphore.is_closed());
+    /// assert!(semaphore.acquire().await.is_err());
+    /// assert_eq!(semaphore.try_acquire().unwrap_err(), TryAcquireError::Closed);
+    /// #
+    /// # })
+    /// ```
+    pub fn close(&self) {
+        if !self.closed.swap(true, Ordering::SeqCst) {
+            for (_, waiter) in self.waiters().iter_mut() {
+                if let Some(w) = waiter.waker.take() {
+                    w.wake();
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the semaphore is closed.
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// Locks the list of waiters.
+    ///
+    /// Wakers are only cloned and woken while the lock is held, so a poisoned lock is ignored.
+    fn waiters(&self) -> MutexGuard<'_, Slab<Waiter>> {
+        self.waiters.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Wakes up the waiters that the available permits can satisfy.
+    ///
+    /// Waiters that have already been woken up count as if they had acquired their permits.
+    fn notify(&self, waiters: &mut Slab<Waiter>) {
+        let mut available = self.permits.load(Ordering::SeqCst);
+
+        for (_, waiter) in waiters.iter_mut() {
+            if waiter.waker.is_none() {
+                available = available.saturating_sub(waiter.permits);
+            } else if waiter.permits <= available {
+                available -= waiter.permits;
+                waiter.waker.take().unwrap().wake();
+            }
+        }
+    }
+
+    /// Takes `n` permits if enough of them are available.
+    fn take(&self, n: usize) -> Result<(), TryAcquireError> {
+        let mut permits = self.permits.load(Ordering::SeqCst);
+        loop {
+            if self.closed.load(Ordering::SeqCst) {
+                return Err(TryAcquireError::Closed);
+            }
+            if permits < n {
+                return Err(TryAcquireError::NoPermits);
+            }
+
+            match self.permits.compare_exchange_weak(
+                permits,
+                permits - n,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => return Ok(()),
+                Err(p) => permits = p,
+            }
+        }
+    }
+}
+
+impl Debug for Semaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Semaphore")
+            .field("permits", &self.available_permits())
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+/// Permits acquired from a [`Semaphore`], which are given back when dropped.
+///
+/// [`Semaphore`]: struct.Semaphore.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+    permits: usize,
+}
+
+impl SemaphorePermit<'_> {
+    /// Returns the number of permits held.
+    pub fn permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Forgets the permits without giving them back to the semaphore.
+    pub fn forget(mut self) {
+        self.permits = 0;
+    }
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        self.semaphore.add_permits(self.permits);
+    }
+}
+
+impl Debug for SemaphorePermit<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SemaphorePermit")
+            .field("permits", &self.permits)
+            .finish()
+    }
+}
+
+/// Permits acquired from a [`Semaphore`] in an [`Arc`], which are given back when dropped.
+///
+/// [`Semaphore`]: struct.Semaphore.html
+/// [`Arc`]: struct.Arc.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct OwnedSemaphorePermit {
+    semaphore: Arc<Semaphore>,
+    permits: usize,
+}
+
+impl OwnedSemaphorePermit {
+    /// Returns the number of permits held.
+    pub fn permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Returns the semaphore the permits were acquired from.
+    pub fn semaphore(&self) -> &Arc<Semaphore> {
+        &self.semaphore
+    }
+
+    /// Forgets the permits without giving them back to the semaphore.
+    pub fn forget(mut self) {
+        self.permits = 0;
+    }
+}
+
+impl Drop for OwnedSemaphorePermit {
+    fn drop(&mut self) {
+        self.semaphore.add_permits(self.permits);
+    }
+}
+
+impl Debug for OwnedSemaphorePermit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedSemaphorePermit")
+            .field("permits", &self.permits)
+            .finish()
+    }
+}
+
+/// An error returned from the `acquire` methods when the semaphore is closed.
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct AcquireError;
+
+impl Error for AcquireError {}
+
+impl Display for AcquireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt("The semaphore is closed.", f)
+    }
+}
+
+/// An error returned from the `try_acquire` methods.
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug, PartialEq, Eq)]
+pub enum TryAcquireError {
+    /// There are not enough permits available.
+    NoPermits,
+
+    /// The semaphore is closed.
+    Closed,
+}
+
+impl Error for TryAcquireError {}
+
+impl Display for TryAcquireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoPermits => Display::fmt("There are not enough permits available.", f),
+            Self::Closed => Display::fmt("The semaphore is closed.", f),
+        }
+    }
+}
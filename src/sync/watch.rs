@@ -0,0 +1,469 @@
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::future::Future;
+use std::mem;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::stream::Stream;
+use crate::sync::WakerSet;
+use crate::task::{Context, Poll};
+
+/// Creates a watch channel, which holds a single value that receivers can watch for changes.
+///
+/// The [`WatchSender`] replaces the value, and every [`WatchReceiver`] can borrow the latest one
+/// and wait for it to be [`changed`]. Receivers only see the latest value: if it is replaced
+/// several times before a receiver looks at it, the receiver sees a single change. This makes
+/// watch channels a good fit for propagating state, such as configuration or health status.
+///
+/// Receivers also implement [`Stream`], yielding a clone of the value every time it changes. When
+/// the sender is dropped, the channel becomes closed, and waiting for a change fails once the
+/// receiver has seen the last value.
+///
+/// [`WatchSender`]: struct.WatchSender.html
+/// [`WatchReceiver`]: struct.WatchReceiver.html
+/// [`changed`]: struct.WatchReceiver.html#method.changed
+/// [`Stream`]: ../stream/trait.Stream.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::watch;
+/// use async_std::task;
+///
+/// let (s, mut r) = watch("initial");
+///
+/// let handle = task::spawn(async move {
+///     while r.changed().await.is_ok() {
+///         println!("config changed to {}", *r.borrow());
+///     }
+/// });
+///
+/// s.send("reloaded");
+/// drop(s);
+/// handle.await.unwrap();
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn watch<T>(init: T) -> (WatchSender<T>, WatchReceiver<T>) {
+    let channel = Arc::new(Channel {
+        value: RwLock::new(init),
+        version: AtomicUsize::new(0),
+        closed: AtomicBool::new(false),
+        receiver_count: AtomicUsize::new(1),
+        wakers: WakerSet::new(),
+    });
+    let s = WatchSender {
+        channel: channel.clone(),
+    };
+    let r = WatchReceiver {
+        channel,
+        seen: 0,
+        opt_key: None,
+    };
+    (s, r)
+}
+
+/// The sending side of a watch channel.
+///
+/// This struct is created by the [`watch`] function. See its documentation for more.
+///
+/// [`watch`]: fn.watch.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct WatchSender<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> WatchSender<T> {
+    /// Replaces the value and notifies all receivers.
+    ///
+    /// The value is replaced even if there are no receivers, so that receivers created later
+    /// with [`subscribe`] see it.
+    ///
+    /// [`subscribe`]: #method.subscribe
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::watch;
+    ///
+    /// let (s, r) = watch(1);
+    /// s.send(2);
+    /// assert_eq!(*r.borrow(), 2);
+    /// ```
+    pub fn send(&self, value: T) {
+        let old = {
+            let mut guard = self.channel.write();
+            self.channel.version.fetch_add(1, Ordering::SeqCst);
+            mem::replace(&mut *guard, value)
+        };
+        drop(old);
+
+        self.channel.wakers.notify_all();
+    }
+
+    /// Borrows the latest value.
+    ///
+    /// The value can't be replaced while it is borrowed, so the returned guard should be dropped
+    /// quickly, and must not be held across an `.await`.
+    pub fn borrow(&self) -> WatchRef<'_, T> {
+        WatchRef(self.channel.read())
+    }
+
+    /// Creates a new receiver, which considers the latest value as already seen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::watch;
+    ///
+    /// let (s, _) = watch(1);
+    /// s.send(2);
+    ///
+    /// let mut r = s.subscribe();
+    /// assert_eq!(*r.borrow(), 2);
+    ///
+    /// s.send(3);
+    /// r.changed().await.unwrap();
+    /// assert_eq!(*r.borrow(), 3);
+    /// #
+    /// # })
+    /// ```
+    pub fn subscribe(&self) -> WatchReceiver<T> {
+        self.channel.receiver_count.fetch_add(1, Ordering::Relaxed);
+
+        WatchReceiver {
+            channel: self.channel.clone(),
+            seen: self.channel.version.load(Ordering::SeqCst),
+            opt_key: None,
+        }
+    }
+
+    /// Returns the number of receivers.
+    pub fn receiver_count(&self) -> usize {
+        self.channel.receiver_count.load(Ordering::SeqCst)
+    }
+}
+
+impl<T> Drop for WatchSender<T> {
+    fn drop(&mut self) {
+        self.channel.closed.store(true, Ordering::SeqCst);
+        self.channel.wakers.notify_all();
+    }
+}
+
+impl<T> Debug for WatchSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("WatchSender { .. }")
+    }
+}
+
+/// The receiving side of a watch channel.
+///
+/// This type can borrow the latest value and wait for it to change. It also implements the
+/// [`Stream`] trait, yielding a clone of the value every time it changes. This struct is created
+/// by the [`watch`] function. See its documentation for more.
+///
+/// [`watch`]: fn.watch.html
+/// [`Stream`]: ../stream/trait.Stream.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::prelude::*;
+/// use async_std::sync::watch;
+/// use async_std::task;
+///
+/// let (s, mut r) = watch(0);
+///
+/// let handle = task::spawn(async move {
+///     let mut last = None;
+///     while let Some(v) = r.next().await {
+///         last = Some(v);
+///     }
+///     last
+/// });
+///
+/// s.send(1);
+/// drop(s);
+///
+/// // The stream may skip values that were replaced before it saw them.
+/// assert_eq!(handle.await.unwrap(), Some(1));
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct WatchReceiver<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+
+    /// The version of the value this receiver has seen.
+    seen: usize,
+
+    /// The key for this receiver in the `channel.wakers` set, used by the `Stream` impl.
+    opt_key: Option<usize>,
+}
+
+impl<T> WatchReceiver<T> {
+    /// Borrows the latest value, without marking it as seen.
+    ///
+    /// The value can't be replaced while it is borrowed, so the returned guard should be dropped
+    /// quickly, and must not be held across an `.await`.
+    pub fn borrow(&self) -> WatchRef<'_, T> {
+        WatchRef(self.channel.read())
+    }
+
+    /// Borrows the latest value and marks it as seen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::watch;
+    ///
+    /// let (s, mut r) = watch(1);
+    /// s.send(2);
+    ///
+    /// assert!(r.has_changed());
+    /// assert_eq!(*r.borrow_and_update(), 2);
+    /// assert!(!r.has_changed());
+    /// ```
+    pub fn borrow_and_update(&mut self) -> WatchRef<'_, T> {
+        let guard = self.channel.read();
+        self.seen = self.channel.version.load(Ordering::SeqCst);
+        WatchRef(guard)
+    }
+
+    /// Returns `true` if the value has changed since it was last seen by this receiver.
+    pub fn has_changed(&self) -> bool {
+        self.channel.version.load(Ordering::SeqCst) != self.seen
+    }
+
+    /// Waits for the value to change, and marks the new value as seen.
+    ///
+    /// Returns immediately if the value has changed since it was last seen. Returns an error if
+    /// the sender has been dropped and the receiver has seen the last value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::watch;
+    /// use async_std::task;
+    ///
+    /// let (s, mut r) = watch(false);
+    ///
+    /// task::spawn(async move {
+    ///     s.send(true);
+    /// });
+    ///
+    /// r.changed().await.unwrap();
+    /// assert!(*r.borrow());
+    /// assert!(r.changed().await.is_err());
+    /// #
+    /// # })
+    /// ```
+    pub async fn changed(&mut self) -> Result<(), WatchRecvError> {
+        struct ChangedFuture<'a, T> {
+            receiver: &'a mut WatchReceiver<T>,
+            opt_key: Option<usize>,
+        }
+
+        impl<T> Future for ChangedFuture<'_, T> {
+            type Output = Result<(), WatchRecvError>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = &mut *self;
+                poll_changed(
+                    &this.receiver.channel,
+                    &mut this.receiver.seen,
+                    &mut this.opt_key,
+                    cx,
+                )
+            }
+        }
+
+        impl<T> Drop for ChangedFuture<'_, T> {
+            fn drop(&mut self) {
+                // All receivers are notified of changes, so just remove this one.
+                if let Some(key) = self.opt_key {
+                    self.receiver.channel.wakers.remove(key);
+                }
+            }
+        }
+
+        ChangedFuture {
+            receiver: self,
+            opt_key: None,
+        }
+        .await
+    }
+}
+
+impl<T> Drop for WatchReceiver<T> {
+    fn drop(&mut self) {
+        // If the current task is still in the set, remove it.
+        if let Some(key) = self.opt_key {
+            self.channel.wakers.remove(key);
+        }
+
+        self.channel.receiver_count.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl<T> Clone for WatchReceiver<T> {
+    fn clone(&self) -> WatchReceiver<T> {
+        self.channel.receiver_count.fetch_add(1, Ordering::Relaxed);
+
+        WatchReceiver {
+            channel: self.channel.clone(),
+            seen: self.seen,
+            opt_key: None,
+        }
+    }
+}
+
+impl<T: Clone> Stream for WatchReceiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let res = futures_core::ready!(poll_changed(
+            &this.channel,
+            &mut this.seen,
+            &mut this.opt_key,
+            cx,
+        ));
+
+        // The version is read again under the same lock as the value, so that a value sent in
+        // the meantime isn't yielded twice.
+        Poll::Ready(res.ok().map(|()| this.borrow_and_update().clone()))
+    }
+}
+
+impl<T> Debug for WatchReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("WatchReceiver { .. }")
+    }
+}
+
+/// A borrowed value of a watch channel.
+///
+/// The value can't be replaced while this guard is alive.
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct WatchRef<'a, T>(RwLockReadGuard<'a, T>);
+
+impl<T> Deref for WatchRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Debug> Debug for WatchRef<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+/// An error returned when waiting for a change of a watch channel whose sender has been dropped.
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchRecvError;
+
+impl Error for WatchRecvError {}
+
+impl Display for WatchRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt("The sender was dropped.", f)
+    }
+}
+
+/// Polls for a change of the value of a watch channel.
+///
+/// If the operation is blocked, the current task will be inserted into the wakers of the channel
+/// and its associated key will then be stored in `opt_key`.
+fn poll_changed<T>(
+    channel: &Channel<T>,
+    seen: &mut usize,
+    opt_key: &mut Option<usize>,
+    cx: &mut Context<'_>,
+) -> Poll<Result<(), WatchRecvError>> {
+    loop {
+        // If the current task is in the set, remove it.
+        if let Some(key) = opt_key.take() {
+            channel.wakers.remove(key);
+        }
+
+        // Check whether the value has changed, or the sender is gone.
+        let version = channel.version.load(Ordering::SeqCst);
+        if version != *seen {
+            *seen = version;
+            return Poll::Ready(Ok(()));
+        }
+        if channel.closed.load(Ordering::SeqCst) {
+            return Poll::Ready(Err(WatchRecvError));
+        }
+
+        // Insert this operation.
+        *opt_key = Some(channel.wakers.insert(cx));
+
+        // If the value is still unchanged and the sender is alive, return.
+        if channel.version.load(Ordering::SeqCst) == *seen && !channel.closed.load(Ordering::SeqCst)
+        {
+            return Poll::Pending;
+        }
+    }
+}
+
+/// The state shared by the sender and the receivers of a watch channel.
+struct Channel<T> {
+    /// The latest value.
+    value: RwLock<T>,
+
+    /// Incremented every time the value is replaced.
+    version: AtomicUsize,
+
+    /// Set when the sender has been dropped.
+    closed: AtomicBool,
+
+    /// The number of receivers.
+    receiver_count: AtomicUsize,
+
+    /// Operations waiting for the value to change.
+    wakers: WakerSet,
+}
+
+impl<T> Channel<T> {
+    /// Locks the value for reading.
+    ///
+    /// The value is only written by swapping it with `mem::replace`, which can't leave it in an
+    /// inconsistent state, so a poisoned lock is ignored instead of spreading the panic.
+    fn read(&self) -> RwLockReadGuard<'_, T> {
+        self.value.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the value for writing.
+    fn write(&self) -> RwLockWriteGuard<'_, T> {
+        self.value.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+unsafe impl<T: Send + Sync> Send for Channel<T> {}
+unsafe impl<T: Send + Sync> Sync for Channel<T> {}
@@ -26,6 +26,7 @@ use crate::task::Builder;
 /// }
 /// ```
 #[cfg(not(target_os = "unknown"))]
+#[track_caller]
 pub fn block_on<F, T>(future: F) -> T
 where
     F: Future<Output = T>,
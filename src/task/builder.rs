@@ -1,24 +1,37 @@
 use std::future::Future;
+#[cfg(not(target_os = "unknown"))]
+use std::panic::{self, AssertUnwindSafe};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+#[cfg(not(target_os = "unknown"))]
+use std::time::Instant;
 
 use pin_project_lite::pin_project;
 
 use crate::io;
-use crate::task::{JoinHandle, Task, TaskLocalsWrapper};
+#[cfg(all(not(target_os = "unknown"), feature = "unstable"))]
+use crate::task::spawn_pinned::Pinned;
+#[cfg(feature = "unstable")]
+use crate::task::CancellationToken;
+#[cfg(not(target_os = "unknown"))]
+use crate::task::Output;
+use crate::task::{JoinHandle, Priority, Task, TaskLocalsWrapper};
 
 /// Task builder that configures the settings of a new task.
 #[derive(Debug, Default)]
 pub struct Builder {
     pub(crate) name: Option<String>,
+    pub(crate) priority: Priority,
+    #[cfg(feature = "unstable")]
+    pub(crate) cancellation_token: Option<CancellationToken>,
 }
 
 impl Builder {
     /// Creates a new builder.
     #[inline]
     pub fn new() -> Builder {
-        Builder { name: None }
+        Builder::default()
     }
 
     /// Configures the name of the task.
@@ -28,7 +41,37 @@ impl Builder {
         self
     }
 
-    fn build<F, T>(self, future: F) -> SupportTaskLocals<F>
+    /// Configures the priority of the task.
+    ///
+    /// Tasks with a higher priority are run first when several tasks are ready. This only
+    /// applies to tasks spawned with [`spawn`].
+    ///
+    /// [`spawn`]: #method.spawn
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    #[inline]
+    pub fn priority(mut self, priority: Priority) -> Builder {
+        self.priority = priority;
+        self
+    }
+
+    /// Attaches a cancellation token to the task.
+    ///
+    /// The token can be retrieved from anywhere within the task with
+    /// [`CancellationToken::current`]. Without this setting, a task spawned from a task that has
+    /// a token gets a child token of it.
+    ///
+    /// [`CancellationToken::current`]: struct.CancellationToken.html#method.current
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    #[inline]
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Builder {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn build<F, T>(self, future: F) -> SupportTaskLocals<F>
     where
         F: Future<Output = T>,
     {
@@ -37,36 +80,132 @@ impl Builder {
         // Create a new task handle.
         let task = Task::new(name);
 
-        #[cfg(not(target_os = "unknown"))]
-        once_cell::sync::Lazy::force(&crate::rt::RUNTIME);
-
         let tag = TaskLocalsWrapper::new(task.clone());
+        #[cfg(feature = "unstable")]
+        let tag = tag.with_cancellation_token(self.cancellation_token);
 
         SupportTaskLocals { tag, future }
     }
 
     /// Spawns a task with the configured settings.
     #[cfg(not(target_os = "unknown"))]
+    #[track_caller]
     pub fn spawn<F, T>(self, future: F) -> io::Result<JoinHandle<T>>
     where
         F: Future<Output = T> + Send + 'static,
         T: Send + 'static,
     {
+        let priority = self.priority;
         let wrapped = self.build(future);
 
         kv_log_macro::trace!("spawn", {
             task_id: wrapped.tag.id().0,
             parent_task_id: TaskLocalsWrapper::get_current(|t| t.id().0).unwrap_or(0),
+            location: &*wrapped.tag.task().location().to_string(),
         });
 
         let task = wrapped.tag.task().clone();
-        let smol_task = smol::Task::spawn(wrapped).into();
+        let output = Output::new(task.clone());
+        let slot = output.slot();
+        let handle = crate::rt::spawn(CatchUnwind::new(wrapped, output), priority)?;
+
+        Ok(JoinHandle::new(handle, slot, task))
+    }
+
+    /// Spawns a task on a worker thread chosen by the runtime, with the configured settings.
+    ///
+    /// This works like [`task::spawn_pinned`].
+    ///
+    /// [`task::spawn_pinned`]: fn.spawn_pinned.html
+    #[cfg(all(not(target_os = "unknown"), feature = "unstable"))]
+    #[track_caller]
+    pub fn spawn_pinned<F, Fut, T>(self, factory: F) -> io::Result<JoinHandle<T>>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + 'static,
+        T: Send + 'static,
+    {
+        self.pinned(None, factory)
+    }
 
-        Ok(JoinHandle::new(smol_task, task))
+    /// Spawns a task on the worker thread with the given index, with the configured settings.
+    ///
+    /// The future is created by `factory` on the worker and only ever runs there, as with
+    /// [`task::spawn_pinned`]. Workers are numbered from zero up to the runtime's thread count.
+    ///
+    /// Returns an error if the runtime has no worker with this index.
+    ///
+    /// [`task::spawn_pinned`]: fn.spawn_pinned.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::cell::Cell;
+    ///
+    /// use async_std::task;
+    ///
+    /// thread_local! {
+    ///     static CALLS: Cell<usize> = Cell::new(0);
+    /// }
+    ///
+    /// for _ in 0..3 {
+    ///     task::Builder::new()
+    ///         .spawn_on(0, || async { CALLS.with(|c| c.set(c.get() + 1)) })
+    ///         .unwrap()
+    ///         .await
+    ///         .unwrap();
+    /// }
+    ///
+    /// // All three tasks ran on the same worker thread.
+    /// let calls = task::Builder::new()
+    ///     .spawn_on(0, || async { CALLS.with(|c| c.get()) })
+    ///     .unwrap()
+    ///     .await
+    ///     .unwrap();
+    /// assert_eq!(calls, 3);
+    /// #
+    /// # })
+    /// ```
+    #[cfg(all(not(target_os = "unknown"), feature = "unstable"))]
+    #[track_caller]
+    pub fn spawn_on<F, Fut, T>(self, worker: usize, factory: F) -> io::Result<JoinHandle<T>>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + 'static,
+        T: Send + 'static,
+    {
+        self.pinned(Some(worker), factory)
+    }
+
+    #[cfg(all(not(target_os = "unknown"), feature = "unstable"))]
+    #[track_caller]
+    fn pinned<F, Fut, T>(self, worker: Option<usize>, factory: F) -> io::Result<JoinHandle<T>>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + 'static,
+        T: Send + 'static,
+    {
+        let wrapped = self.build(Pinned::new(factory));
+
+        kv_log_macro::trace!("spawn_pinned", {
+            task_id: wrapped.tag.id().0,
+            parent_task_id: TaskLocalsWrapper::get_current(|t| t.id().0).unwrap_or(0),
+            location: &*wrapped.tag.task().location().to_string(),
+        });
+
+        let task = wrapped.tag.task().clone();
+        let output = Output::new(task.clone());
+        let slot = output.slot();
+        let handle = crate::rt::spawn_on(worker, CatchUnwind::new(wrapped, output))?;
+
+        Ok(JoinHandle::new(handle, slot, task))
     }
 
     /// Spawns a task locally with the configured settings.
     #[cfg(all(not(target_os = "unknown"), feature = "unstable"))]
+    #[track_caller]
     pub fn local<F, T>(self, future: F) -> io::Result<JoinHandle<T>>
     where
         F: Future<Output = T> + 'static,
@@ -77,16 +216,20 @@ impl Builder {
         kv_log_macro::trace!("spawn_local", {
             task_id: wrapped.tag.id().0,
             parent_task_id: TaskLocalsWrapper::get_current(|t| t.id().0).unwrap_or(0),
+            location: &*wrapped.tag.task().location().to_string(),
         });
 
         let task = wrapped.tag.task().clone();
-        let smol_task = smol::Task::local(wrapped).into();
+        let output = Output::new(task.clone());
+        let slot = output.slot();
+        let smol_task = smol::Task::local(CatchUnwind::new(wrapped, output)).into();
 
-        Ok(JoinHandle::new(smol_task, task))
+        Ok(JoinHandle::new(smol_task, slot, task))
     }
 
     /// Spawns a task locally with the configured settings.
     #[cfg(all(target_arch = "wasm32", feature = "unstable"))]
+    #[track_caller]
     pub fn local<F, T>(self, future: F) -> io::Result<JoinHandle<T>>
     where
         F: Future<Output = T> + 'static,
@@ -102,6 +245,7 @@ impl Builder {
         kv_log_macro::trace!("spawn_local", {
             task_id: wrapped.tag.id().0,
             parent_task_id: TaskLocalsWrapper::get_current(|t| t.id().0).unwrap_or(0),
+            location: &*wrapped.tag.task().location().to_string(),
         });
 
         let task = wrapped.tag.task().clone();
@@ -112,6 +256,7 @@ impl Builder {
 
     /// Spawns a task locally with the configured settings.
     #[cfg(all(target_arch = "wasm32", not(feature = "unstable")))]
+    #[track_caller]
     pub(crate) fn local<F, T>(self, future: F) -> io::Result<JoinHandle<T>>
     where
         F: Future<Output = T> + 'static,
@@ -128,6 +273,7 @@ impl Builder {
         kv_log_macro::trace!("spawn_local", {
             task_id: wrapped.tag.id().0,
             parent_task_id: TaskLocalsWrapper::get_current(|t| t.id().0).unwrap_or(0),
+            location: &*wrapped.tag.task().location().to_string(),
         });
 
         let task = wrapped.tag.task().clone();
@@ -138,6 +284,7 @@ impl Builder {
 
     /// Spawns a task with the configured settings, blocking on its execution.
     #[cfg(not(target_os = "unknown"))]
+    #[track_caller]
     pub fn blocking<F, T>(self, future: F) -> T
     where
         F: Future<Output = T>,
@@ -148,6 +295,7 @@ impl Builder {
         kv_log_macro::trace!("block_on", {
             task_id: wrapped.tag.id().0,
             parent_task_id: TaskLocalsWrapper::get_current(|t| t.id().0).unwrap_or(0),
+            location: &*wrapped.tag.task().location().to_string(),
         });
 
         // Run the future as a task.
@@ -157,8 +305,8 @@ impl Builder {
 
 pin_project! {
     /// Wrapper to add support for task locals.
-    struct SupportTaskLocals<F> {
-        tag: TaskLocalsWrapper,
+    pub(crate) struct SupportTaskLocals<F> {
+        pub(crate) tag: TaskLocalsWrapper,
         #[pin]
         future: F,
     }
@@ -168,11 +316,73 @@ impl<F: Future> Future for SupportTaskLocals<F> {
     type Output = F::Output;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        unsafe {
-            TaskLocalsWrapper::set_current(&self.tag, || {
-                let this = self.project();
-                this.future.poll(cx)
-            })
+        let this = self.project();
+        let tag: &TaskLocalsWrapper = this.tag;
+        let future = this.future;
+
+        tag.record_poll();
+
+        // Time the poll if the runtime watches for slow polls.
+        #[cfg(not(target_os = "unknown"))]
+        let start = crate::rt::slow_poll_threshold().map(|threshold| (threshold, Instant::now()));
+
+        let poll = unsafe { TaskLocalsWrapper::set_current(tag, || future.poll(cx)) };
+
+        #[cfg(not(target_os = "unknown"))]
+        {
+            if let Some((threshold, start)) = start {
+                let elapsed = start.elapsed();
+                if elapsed > threshold {
+                    kv_log_macro::warn!("slow poll", {
+                        task_id: tag.id().0,
+                        task_name: tag.task().name().unwrap_or(""),
+                        elapsed_ms: elapsed.as_millis() as u64,
+                    });
+                }
+            }
         }
+
+        poll
+    }
+}
+
+#[cfg(not(target_os = "unknown"))]
+pin_project! {
+    /// Wrapper that catches panics of a spawned task and handles them according to the panic
+    /// policy of the runtime.
+    ///
+    /// The result of the task is stored in its `Output`, which is dropped after the future.
+    struct CatchUnwind<F, T> {
+        #[pin]
+        future: SupportTaskLocals<F>,
+        output: Output<T>,
+    }
+}
+
+#[cfg(not(target_os = "unknown"))]
+impl<F, T> CatchUnwind<F, T> {
+    fn new(future: SupportTaskLocals<F>, output: Output<T>) -> CatchUnwind<F, T> {
+        CatchUnwind { future, output }
+    }
+}
+
+#[cfg(not(target_os = "unknown"))]
+impl<F: Future<Output = T>, T> Future for CatchUnwind<F, T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let mut future = this.future;
+
+        let res = match panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx))) {
+            Ok(Poll::Pending) => return Poll::Pending,
+            Ok(Poll::Ready(val)) => Ok(val),
+            Err(payload) => {
+                crate::rt::handle_panic(future.tag.task(), &*payload);
+                Err(payload)
+            }
+        };
+        this.output.set(res);
+        Poll::Ready(())
     }
 }
@@ -0,0 +1,244 @@
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+use crate::sync::WakerSet;
+use crate::task::{Context, Poll, TaskLocalsWrapper};
+
+/// A token for cooperative cancellation.
+///
+/// Cancelling a task with [`JoinHandle::cancel`] drops it at its next `.await`, without giving it
+/// a chance to clean up. A `CancellationToken` instead lets the task observe that it was asked to
+/// stop, by checking [`is_cancelled`] or awaiting [`cancelled`], and wind down on its own terms.
+///
+/// Clones of a token share the same state. [`child_token`] creates a token that gets cancelled
+/// along with its parent, but can also be cancelled on its own without affecting the parent. This
+/// makes it possible to cancel a whole tree of tasks, or only one of its branches.
+///
+/// A token can be attached to a task with [`Builder::cancellation_token`], and then retrieved from
+/// anywhere in the task with [`CancellationToken::current`], without being passed down explicitly.
+/// Tasks spawned from it, including blocking ones, inherit a child token of it, so cancelling the
+/// token reaches the whole tree of tasks.
+///
+/// [`JoinHandle::cancel`]: struct.JoinHandle.html#method.cancel
+/// [`is_cancelled`]: #method.is_cancelled
+/// [`cancelled`]: #method.cancelled
+/// [`child_token`]: #method.child_token
+/// [`Builder::cancellation_token`]: struct.Builder.html#method.cancellation_token
+/// [`CancellationToken::current`]: #method.current
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::task::{self, CancellationToken};
+///
+/// let token = CancellationToken::new();
+///
+/// let child = token.child_token();
+/// let handle = task::spawn(async move {
+///     child.cancelled().await;
+///     "cleaned up"
+/// });
+///
+/// token.cancel();
+/// assert_eq!(handle.await.unwrap(), "cleaned up");
+/// #
+/// # })
+/// ```
+#[derive(Clone)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    /// Set once the token is cancelled.
+    cancelled: AtomicBool,
+
+    /// Tasks waiting for the token to be cancelled.
+    wakers: WakerSet,
+
+    /// Tokens to cancel along with this one.
+    children: Mutex<Vec<Weak<Inner>>>,
+
+    /// The token this one is a child of.
+    ///
+    /// Children are only weakly referenced by their parent, so this keeps an intermediate token
+    /// alive as long as its own children need it to forward cancellation.
+    _parent: Option<Arc<Inner>>,
+}
+
+unsafe impl Send for Inner {}
+unsafe impl Sync for Inner {}
+
+impl CancellationToken {
+    /// Creates a new token.
+    pub fn new() -> CancellationToken {
+        CancellationToken::with_parent(None)
+    }
+
+    /// Creates a token that is not cancelled, with an optional parent.
+    fn with_parent(parent: Option<Arc<Inner>>) -> CancellationToken {
+        CancellationToken {
+            inner: Arc::new(Inner {
+                cancelled: AtomicBool::new(false),
+                wakers: WakerSet::new(),
+                children: Mutex::new(Vec::new()),
+                _parent: parent,
+            }),
+        }
+    }
+
+    /// Returns the token attached to the current task, if any.
+    ///
+    /// A token is attached to a task with [`Builder::cancellation_token`], or inherited as a child
+    /// token from the task that spawned it.
+    ///
+    /// [`Builder::cancellation_token`]: struct.Builder.html#method.cancellation_token
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::task::{self, CancellationToken};
+    ///
+    /// async fn deep_in_the_call_tree() -> bool {
+    ///     CancellationToken::current().map_or(false, |t| t.is_cancelled())
+    /// }
+    ///
+    /// let token = CancellationToken::new();
+    /// token.cancel();
+    ///
+    /// let handle = task::Builder::new()
+    ///     .cancellation_token(token)
+    ///     .spawn(deep_in_the_call_tree())
+    ///     .unwrap();
+    /// assert!(handle.await.unwrap());
+    /// #
+    /// # })
+    /// ```
+    pub fn current() -> Option<CancellationToken> {
+        TaskLocalsWrapper::get_current(|t| t.cancellation_token().cloned()).unwrap_or(None)
+    }
+
+    /// Creates a token that gets cancelled when this one is.
+    ///
+    /// Cancelling the child token doesn't affect this one. If this token is already cancelled,
+    /// the child token is created cancelled.
+    pub fn child_token(&self) -> CancellationToken {
+        // Checking the flag with the lock held makes sure `cancel` sees the child.
+        let mut children = self.inner.children.lock().unwrap();
+        if self.is_cancelled() {
+            let child = CancellationToken::new();
+            child.inner.cancelled.store(true, Ordering::SeqCst);
+            child
+        } else {
+            let child = CancellationToken::with_parent(Some(self.inner.clone()));
+            children.retain(|c| c.strong_count() > 0);
+            children.push(Arc::downgrade(&child.inner));
+            child
+        }
+    }
+
+    /// Cancels this token and all of its child tokens.
+    ///
+    /// Tasks waiting in [`cancelled`] are woken up. Cancelling a token more than once has no
+    /// effect.
+    ///
+    /// [`cancelled`]: #method.cancelled
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+
+    /// Returns `true` if this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Waits until this token is cancelled.
+    ///
+    /// Completes immediately if the token is already cancelled.
+    pub async fn cancelled(&self) {
+        struct CancelledFuture<'a> {
+            token: &'a CancellationToken,
+            opt_key: Option<usize>,
+        }
+
+        impl Future for CancelledFuture<'_> {
+            type Output = ();
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                loop {
+                    // If the current task is in the set, remove it.
+                    if let Some(key) = self.opt_key.take() {
+                        self.token.inner.wakers.remove(key);
+                    }
+
+                    if self.token.is_cancelled() {
+                        return Poll::Ready(());
+                    }
+
+                    self.opt_key = Some(self.token.inner.wakers.insert(cx));
+
+                    // If the token is still not cancelled, return.
+                    if !self.token.is_cancelled() {
+                        return Poll::Pending;
+                    }
+                }
+            }
+        }
+
+        impl Drop for CancelledFuture<'_> {
+            fn drop(&mut self) {
+                if let Some(key) = self.opt_key {
+                    self.token.inner.wakers.remove(key);
+                }
+            }
+        }
+
+        CancelledFuture {
+            token: self,
+            opt_key: None,
+        }
+        .await
+    }
+}
+
+impl Inner {
+    fn cancel(&self) {
+        let children = {
+            let mut children = self.children.lock().unwrap();
+            if self.cancelled.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            mem::take(&mut *children)
+        };
+
+        self.wakers.notify_all();
+
+        for child in children {
+            if let Some(child) = child.upgrade() {
+                child.cancel();
+            }
+        }
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> CancellationToken {
+        CancellationToken::new()
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("is_cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
@@ -0,0 +1,31 @@
+use crate::task::{registry, TaskInfo};
+
+/// Returns information about all live tasks.
+///
+/// A task is live from the moment it is spawned until it completes or is cancelled. This
+/// includes tasks spawned with [`spawn`], [`spawn_local`] and [`Builder`], as well as the tasks
+/// created by [`block_on`]. The tasks are sorted from the oldest to the newest.
+///
+/// This is useful to find out which tasks are stuck when an application hangs.
+///
+/// [`spawn`]: fn.spawn.html
+/// [`spawn_local`]: fn.spawn_local.html
+/// [`Builder`]: struct.Builder.html
+/// [`block_on`]: fn.block_on.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::task;
+///
+/// for info in task::dump() {
+///     println!("{}", info);
+/// }
+/// #
+/// # })
+/// ```
+pub fn dump() -> Vec<TaskInfo> {
+    registry::snapshot()
+}
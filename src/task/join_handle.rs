@@ -1,29 +1,105 @@
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
 use std::future::Future;
+use std::panic;
 use std::pin::Pin;
+#[cfg(not(target_os = "unknown"))]
+use std::sync::Arc;
+use std::sync::{Mutex, PoisonError};
+#[cfg(not(target_os = "unknown"))]
+use std::thread;
+#[cfg(feature = "unstable")]
+use std::time::Duration;
 
 use crate::task::{Context, Poll, Task};
+use crate::utils;
 
 /// A handle that awaits the result of a task.
 ///
 /// Dropping a [`JoinHandle`] will detach the task, meaning that there is no longer
 /// a handle to the task and no way to `join` on it.
 ///
+/// With the `unstable` feature, awaiting a [`JoinHandle`] returns a [`JoinError`] if the task
+/// panicked or was cancelled. Otherwise awaiting it resumes the panic of the task, and panics if
+/// the task was cancelled.
+///
 /// Created when a task is [spawned].
 ///
 /// [spawned]: fn.spawn.html
+/// [`JoinError`]: struct.JoinError.html
 #[derive(Debug)]
 pub struct JoinHandle<T> {
+    #[cfg(not(target_os = "unknown"))]
+    handle: Option<InnerHandle>,
+    #[cfg(target_arch = "wasm32")]
     handle: Option<InnerHandle<T>>,
+    #[cfg(not(target_os = "unknown"))]
+    slot: Slot<T>,
     task: Task,
 }
 
 #[cfg(not(target_os = "unknown"))]
-type InnerHandle<T> = async_task::JoinHandle<T, ()>;
+type InnerHandle = async_task::JoinHandle<(), ()>;
 #[cfg(target_arch = "wasm32")]
 type InnerHandle<T> = futures_channel::oneshot::Receiver<T>;
 
+/// The result of a task, shared between the task and its [`JoinHandle`].
+#[cfg(not(target_os = "unknown"))]
+pub(crate) type Slot<T> = Arc<Mutex<Option<thread::Result<T>>>>;
+
+/// The task's end of its result slot.
+///
+/// This lives next to the task's future and is dropped after it, and only then marks the task
+/// finished. A finished task has therefore dropped all its state and stored its result, unless it
+/// was cancelled before it could produce one.
+#[cfg(not(target_os = "unknown"))]
+pub(crate) struct Output<T> {
+    slot: Slot<T>,
+    task: Task,
+}
+
+#[cfg(not(target_os = "unknown"))]
+impl<T> Output<T> {
+    /// Creates the result slot of a task.
+    pub(crate) fn new(task: Task) -> Output<T> {
+        Output {
+            slot: Arc::new(Mutex::new(None)),
+            task,
+        }
+    }
+
+    /// Returns the slot, to be handed to the [`JoinHandle`].
+    pub(crate) fn slot(&self) -> Slot<T> {
+        self.slot.clone()
+    }
+
+    /// Stores the result of the task.
+    pub(crate) fn set(&self, res: thread::Result<T>) {
+        *self.slot.lock().unwrap_or_else(PoisonError::into_inner) = Some(res);
+    }
+}
+
+#[cfg(not(target_os = "unknown"))]
+impl<T> Drop for Output<T> {
+    fn drop(&mut self) {
+        self.task.finish();
+    }
+}
+
 impl<T> JoinHandle<T> {
     /// Creates a new `JoinHandle`.
+    #[cfg(not(target_os = "unknown"))]
+    pub(crate) fn new(inner: InnerHandle, slot: Slot<T>, task: Task) -> JoinHandle<T> {
+        JoinHandle {
+            handle: Some(inner),
+            slot,
+            task,
+        }
+    }
+
+    /// Creates a new `JoinHandle`.
+    #[cfg(target_arch = "wasm32")]
     pub(crate) fn new(inner: InnerHandle<T>, task: Task) -> JoinHandle<T> {
         JoinHandle {
             handle: Some(inner),
@@ -50,12 +126,117 @@ impl<T> JoinHandle<T> {
         &self.task
     }
 
+    /// Waits for the task to complete, returning an error if it panicked or was cancelled.
+    ///
+    /// This is the same as awaiting the handle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::task;
+    ///
+    /// let handle = task::spawn(async {
+    ///     panic!("boom");
+    /// });
+    ///
+    /// let err = handle.join().await.unwrap_err();
+    /// assert!(err.is_panic());
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn join(mut self) -> Result<T, JoinError> {
+        crate::future::poll_fn(|cx| self.poll_join(cx)).await
+    }
+
+    /// Waits at most `dur` for the task to complete.
+    ///
+    /// Returns the result of the task, as [`join`] would, if it completes in time. Otherwise the
+    /// handle is given back, and the task keeps running so it can be checked on again later.
+    ///
+    /// [`join`]: #method.join
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::task;
+    ///
+    /// let handle = task::spawn(async {
+    ///     task::sleep(Duration::from_millis(100)).await;
+    ///     1 + 2
+    /// });
+    ///
+    /// let handle = handle
+    ///     .join_timeout(Duration::from_millis(1))
+    ///     .await
+    ///     .unwrap_err();
+    /// assert_eq!(handle.await.unwrap(), 3);
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn join_timeout(
+        mut self,
+        dur: Duration,
+    ) -> Result<Result<T, JoinError>, JoinHandle<T>> {
+        let join = crate::future::poll_fn(|cx| self.poll_join(cx));
+        let res = crate::future::timeout(dur, join).await;
+        res.map_err(|_| self)
+    }
+
+    /// Returns `true` if the task has finished.
+    ///
+    /// A task is finished once it has completed, panicked or been cancelled. This doesn't poll or
+    /// consume the handle, so the result of the task can still be awaited afterwards, and is then
+    /// available without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::task;
+    ///
+    /// let handle = task::spawn(async {});
+    /// while !handle.is_finished() {
+    ///     task::sleep(Duration::from_millis(1)).await;
+    /// }
+    /// handle.await.unwrap();
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Detaches the task, letting it run in the background.
+    ///
+    /// This is the same as dropping the handle, but makes the intent explicit.
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn detach(self) {
+        drop(self);
+    }
+
     /// Cancel this task.
     #[cfg(not(target_os = "unknown"))]
     pub async fn cancel(mut self) -> Option<T> {
         let handle = self.handle.take().unwrap();
         handle.cancel();
-        handle.await
+        handle.await;
+        self.take_result().and_then(Result::ok)
     }
 
     /// Cancel this task.
@@ -65,17 +246,180 @@ impl<T> JoinHandle<T> {
         handle.close();
         handle.await.ok()
     }
+
+    /// Cancels the task without waiting for it.
+    #[cfg(all(not(target_os = "unknown"), feature = "unstable"))]
+    pub(crate) fn abort(&self) {
+        if let Some(handle) = &self.handle {
+            handle.cancel();
+        }
+    }
+
+    /// Awaits the output of the task, resuming its panic if it panicked.
+    ///
+    /// This is how the crate awaits its own tasks, whose output doesn't depend on the `unstable`
+    /// feature.
+    #[cfg(not(target_os = "unknown"))]
+    pub(crate) async fn output(mut self) -> T {
+        crate::future::poll_fn(|cx| self.poll_output(cx)).await
+    }
+
+    /// Polls the task for its output, resuming its panic if it panicked.
+    #[cfg_attr(all(feature = "unstable", target_os = "unknown"), allow(dead_code))]
+    pub(crate) fn poll_output(&mut self, cx: &mut Context<'_>) -> Poll<T> {
+        match self.poll_join(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Ok(val)) => Poll::Ready(val),
+            Poll::Ready(Err(err)) => match err.into_panic() {
+                Some(payload) => panic::resume_unwind(payload),
+                None => panic!("cannot await the result of a cancelled task"),
+            },
+        }
+    }
+
+    /// Polls the task for its output.
+    #[cfg(not(target_os = "unknown"))]
+    pub(crate) fn poll_join(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, JoinError>> {
+        // A finished task has already stored its result, even if the runtime has yet to report
+        // its completion to the inner handle.
+        if !self.task.is_finished() {
+            futures_core::ready!(Pin::new(self.handle.as_mut().unwrap()).poll(cx));
+        }
+
+        Poll::Ready(match self.take_result() {
+            Some(Ok(val)) => Ok(val),
+            Some(Err(payload)) => Err(JoinError::panic(payload)),
+            None => Err(JoinError::cancelled()),
+        })
+    }
+
+    /// Takes the result the task stored, if it stored one.
+    #[cfg(not(target_os = "unknown"))]
+    fn take_result(&self) -> Option<thread::Result<T>> {
+        self.slot
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+
+    /// Polls the task for its output.
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn poll_join(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, JoinError>> {
+        match Pin::new(self.handle.as_mut().unwrap()).poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Ok(val)) => Poll::Ready(Ok(val)),
+            Poll::Ready(Err(_)) => Poll::Ready(Err(JoinError::cancelled())),
+        }
+    }
 }
 
+/// Awaits the output of the task.
+///
+/// # Panics
+///
+/// Resumes the panic of the task if it panicked, and panics if the task was cancelled. The
+/// `unstable` feature returns these as a [`JoinError`] instead.
+///
+/// [`JoinError`]: struct.JoinError.html
+#[cfg(not(feature = "unstable"))]
 impl<T> Future for JoinHandle<T> {
     type Output = T;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        match Pin::new(&mut self.handle.as_mut().unwrap()).poll(cx) {
-            Poll::Pending => Poll::Pending,
-            Poll::Ready(output) => {
-                Poll::Ready(output.expect("cannot await the result of a panicked task"))
+        self.poll_output(cx)
+    }
+}
+
+/// Awaits the result of the task, which is an error if the task panicked or was cancelled.
+#[cfg(feature = "unstable")]
+impl<T> Future for JoinHandle<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.poll_join(cx)
+    }
+}
+
+/// The error returned by [`JoinHandle::join`] when a task doesn't complete.
+///
+/// A task doesn't complete if it panics, or if it gets cancelled, for example because its runtime
+/// was shut down.
+///
+/// [`JoinHandle::join`]: struct.JoinHandle.html#method.join
+pub struct JoinError {
+    repr: Repr,
+}
+
+enum Repr {
+    /// The panic payload, behind a mutex so that the error is `Sync`.
+    Panic(Mutex<Box<dyn Any + Send>>),
+    Cancelled,
+}
+
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+impl JoinError {
+    fn panic(payload: Box<dyn Any + Send>) -> JoinError {
+        JoinError {
+            repr: Repr::Panic(Mutex::new(payload)),
+        }
+    }
+
+    pub(crate) fn cancelled() -> JoinError {
+        JoinError {
+            repr: Repr::Cancelled,
+        }
+    }
+
+    /// Returns `true` if the task panicked.
+    pub fn is_panic(&self) -> bool {
+        match self.repr {
+            Repr::Panic(_) => true,
+            Repr::Cancelled => false,
+        }
+    }
+
+    /// Returns `true` if the task was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        match self.repr {
+            Repr::Panic(_) => false,
+            Repr::Cancelled => true,
+        }
+    }
+
+    /// Returns the panic payload if the task panicked.
+    ///
+    /// The payload can be passed to [`std::panic::resume_unwind`] to propagate the panic.
+    ///
+    /// [`std::panic::resume_unwind`]: https://doc.rust-lang.org/std/panic/fn.resume_unwind.html
+    pub fn into_panic(self) -> Option<Box<dyn Any + Send>> {
+        match self.repr {
+            Repr::Panic(payload) => {
+                Some(payload.into_inner().unwrap_or_else(PoisonError::into_inner))
             }
+            Repr::Cancelled => None,
         }
     }
 }
+
+impl fmt::Debug for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.repr {
+            Repr::Panic(_) => f.pad("JoinError::Panic(..)"),
+            Repr::Cancelled => f.pad("JoinError::Cancelled"),
+        }
+    }
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.repr {
+            Repr::Panic(payload) => {
+                let payload = payload.lock().unwrap_or_else(PoisonError::into_inner);
+                write!(f, "task panicked: {}", utils::panic_message(&**payload))
+            }
+            Repr::Cancelled => f.write_str("task was cancelled"),
+        }
+    }
+}
+
+impl Error for JoinError {}
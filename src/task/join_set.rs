@@ -0,0 +1,163 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::stream::Stream;
+use crate::task::{Context, JoinError, JoinHandle, Poll};
+
+/// A collection of tasks that yields their results as they complete.
+///
+/// Tasks are added to the set with [`spawn`], or by handing over the [`JoinHandle`] of a task that
+/// was spawned elsewhere with [`push`]. Their results are then received in the order in which the
+/// tasks complete, either with [`join_next`] or by using the set as a [`Stream`].
+///
+/// Dropping the set cancels all the tasks that are still in it.
+///
+/// [`spawn`]: #method.spawn
+/// [`push`]: #method.push
+/// [`join_next`]: #method.join_next
+/// [`JoinHandle`]: struct.JoinHandle.html
+/// [`Stream`]: ../stream/trait.Stream.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::task::JoinSet;
+///
+/// let mut set = JoinSet::new();
+/// for i in 0..3 {
+///     set.spawn(async move { i * 2 });
+/// }
+///
+/// let mut sum = 0;
+/// while let Some(res) = set.join_next().await {
+///     sum += res.unwrap();
+/// }
+/// assert_eq!(sum, 6);
+/// #
+/// # })
+/// ```
+pub struct JoinSet<T> {
+    handles: Vec<JoinHandle<T>>,
+}
+
+impl<T> JoinSet<T> {
+    /// Creates an empty set.
+    pub fn new() -> JoinSet<T> {
+        JoinSet {
+            handles: Vec::new(),
+        }
+    }
+
+    /// Spawns a task and adds it to the set.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the task can't be spawned, like [`task::spawn`].
+    ///
+    /// [`task::spawn`]: fn.spawn.html
+    #[track_caller]
+    pub fn spawn<F>(&mut self, future: F)
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.push(crate::task::spawn(future));
+    }
+
+    /// Adds an already spawned task to the set.
+    pub fn push(&mut self, handle: JoinHandle<T>) {
+        self.handles.push(handle);
+    }
+
+    /// Returns the number of tasks in the set.
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Returns `true` if the set contains no tasks.
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Waits for the next task in the set to complete, and removes it from the set.
+    ///
+    /// Returns `None` if the set is empty. A task that panicked or got cancelled yields a
+    /// [`JoinError`].
+    ///
+    /// [`JoinError`]: struct.JoinError.html
+    pub async fn join_next(&mut self) -> Option<Result<T, JoinError>> {
+        crate::future::poll_fn(|cx| self.poll_join_next(cx)).await
+    }
+
+    /// Cancels all the tasks in the set and removes them from it.
+    ///
+    /// Tasks that are being polled right now are cancelled as soon as their poll returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::future;
+    /// use async_std::task::JoinSet;
+    ///
+    /// let mut set = JoinSet::new();
+    /// set.spawn(future::pending::<()>());
+    ///
+    /// set.abort_all();
+    /// assert!(set.is_empty());
+    /// #
+    /// # })
+    /// ```
+    pub fn abort_all(&mut self) {
+        for handle in self.handles.drain(..) {
+            handle.abort();
+        }
+    }
+
+    /// Polls the tasks in the set for the next output.
+    fn poll_join_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<T, JoinError>>> {
+        if self.handles.is_empty() {
+            return Poll::Ready(None);
+        }
+
+        for i in 0..self.handles.len() {
+            if let Poll::Ready(res) = self.handles[i].poll_join(cx) {
+                self.handles.swap_remove(i);
+                return Poll::Ready(Some(res));
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> Default for JoinSet<T> {
+    fn default() -> JoinSet<T> {
+        JoinSet::new()
+    }
+}
+
+impl<T> Drop for JoinSet<T> {
+    fn drop(&mut self) {
+        self.abort_all();
+    }
+}
+
+impl<T> Stream for JoinSet<T> {
+    type Item = Result<T, JoinError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_join_next(cx)
+    }
+}
+
+impl<T> fmt::Debug for JoinSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinSet")
+            .field("len", &self.handles.len())
+            .finish()
+    }
+}
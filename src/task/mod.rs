@@ -17,9 +17,12 @@
 //! tasks using the atomically-reference-counted container, [`Arc`].
 //!
 //! Fatal logic errors in Rust cause *thread panic*, during which a thread will unwind the stack,
-//! running destructors and freeing owned resources. If a panic occurs inside a task, there is no
-//! meaningful way of recovering, so the panic will propagate through any thread boundaries all the
-//! way to the root task. This is also known as a "panic = abort" model.
+//! running destructors and freeing owned resources. If a panic occurs inside a spawned task, it
+//! is stopped at the boundary of the task, so that it doesn't take down the thread running it,
+//! and is then handled according to the runtime's panic policy. Awaiting the [`JoinHandle`] of a
+//! task that panicked resumes the panic in the awaiting task, so by default it propagates all the
+//! way to the root task. With the `unstable` feature, the panic is returned as a [`JoinError`]
+//! instead.
 //!
 //! ## Spawning a task
 //!
@@ -94,7 +97,16 @@
 //! value that is `'static` (no borrowed pointers). It provides an accessor function,
 //! [`with`], that yields a shared reference to the value to the specified
 //! closure. Task-local keys allow only shared access to values, as there would be no
-//! way to guarantee uniqueness if mutable borrows were allowed.
+//! way to guarantee uniqueness if mutable borrows were allowed. A value can instead be replaced
+//! as a whole with [`set`] or [`replace`], or for the duration of a future with [`scope`].
+#![cfg_attr(
+    feature = "unstable",
+    doc = r#"
+Task-local values start out freshly initialized in every task, unless they are declared as
+inheritable, in which case a spawned task starts with a clone of its parent's value. This is
+useful for context that should follow work onto subtasks, such as request IDs.
+"#
+)]
 //!
 //! ## Naming tasks
 //!
@@ -108,6 +120,7 @@
 //! [`JoinHandle`]: struct.JoinHandle.html
 //! [`JoinHandle::task`]: struct.JoinHandle.html#method.task
 //! [`join`]: struct.JoinHandle.html#method.join
+//! [`JoinError`]: struct.JoinError.html
 //! [`panic!`]: https://doc.rust-lang.org/std/macro.panic.html
 //! [`Builder`]: struct.Builder.html
 //! [`Builder::name`]: struct.Builder.html#method.name
@@ -116,6 +129,9 @@
 //! [`Task::name`]: struct.Task.html#method.name
 //! [`task_local!`]: ../macro.task_local.html
 //! [`with`]: struct.LocalKey.html#method.with
+//! [`set`]: struct.LocalKey.html#method.set
+//! [`replace`]: struct.LocalKey.html#method.replace
+//! [`scope`]: struct.LocalKey.html#method.scope
 
 cfg_alloc! {
     #[doc(inline)]
@@ -142,6 +158,8 @@ cfg_default! {
     pub use spawn::spawn;
     pub use task_local::{AccessError, LocalKey};
 
+    #[cfg(not(target_os = "unknown"))]
+    pub(crate) use join_handle::Output;
     pub(crate) use task_local::LocalsMap;
     pub(crate) use task_locals_wrapper::TaskLocalsWrapper;
 
@@ -149,6 +167,7 @@ cfg_default! {
     mod builder;
     mod current;
     mod join_handle;
+    mod priority;
     mod sleep;
     #[cfg(not(target_os = "unknown"))]
     mod spawn;
@@ -165,6 +184,11 @@ cfg_default! {
     #[cfg(not(target_os = "unknown"))]
     #[cfg(not(any(feature = "unstable", test)))]
     pub(crate) use spawn_blocking::spawn_blocking;
+
+    #[cfg(feature = "unstable")]
+    pub use priority::Priority;
+    #[cfg(not(feature = "unstable"))]
+    pub(crate) use priority::Priority;
 }
 
 cfg_unstable! {
@@ -172,3 +196,35 @@ cfg_unstable! {
 
     mod spawn_local;
 }
+
+cfg_unstable_default! {
+    pub use cancellation_token::CancellationToken;
+    pub use join_handle::JoinError;
+
+    #[cfg(not(target_os = "unknown"))]
+    pub use dump::dump;
+    #[cfg(not(target_os = "unknown"))]
+    pub use join_set::JoinSet;
+    pub use scope::{scope, Scope, ScopedJoinHandle};
+    #[cfg(not(target_os = "unknown"))]
+    pub use spawn_pinned::spawn_pinned;
+    #[cfg(not(target_os = "unknown"))]
+    pub use supervisor::{Supervisor, SupervisorError};
+    #[cfg(not(target_os = "unknown"))]
+    pub use task_info::TaskInfo;
+
+    mod cancellation_token;
+    #[cfg(not(target_os = "unknown"))]
+    mod dump;
+    #[cfg(not(target_os = "unknown"))]
+    mod join_set;
+    #[cfg(not(target_os = "unknown"))]
+    mod registry;
+    mod scope;
+    #[cfg(not(target_os = "unknown"))]
+    mod spawn_pinned;
+    #[cfg(not(target_os = "unknown"))]
+    mod supervisor;
+    #[cfg(not(target_os = "unknown"))]
+    mod task_info;
+}
@@ -0,0 +1,58 @@
+/// The priority of a task.
+///
+/// When several tasks are ready to run, the runtime runs the ones with the highest priority
+/// first. Tasks with the same priority run in the order in which they were scheduled. A task only
+/// runs when no task with a higher priority is ready, so a steady stream of high-priority work can
+/// delay low-priority tasks indefinitely.
+///
+/// The priority of a task is configured with [`Builder::priority`], and defaults to
+/// [`Priority::Normal`].
+///
+/// [`Builder::priority`]: struct.Builder.html#method.priority
+/// [`Priority::Normal`]: #variant.Normal
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "unstable")]
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::task::{self, Priority};
+///
+/// let handle = task::Builder::new()
+///     .priority(Priority::Low)
+///     .spawn(async {
+///         // background compaction
+///     })
+///     .unwrap();
+/// handle.await.unwrap();
+/// #
+/// # })
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+pub enum Priority {
+    /// For background work that can wait.
+    Low,
+
+    /// The default priority.
+    #[default]
+    Normal,
+
+    /// For latency-sensitive work.
+    High,
+}
+
+impl Priority {
+    /// The number of priority levels.
+    pub(crate) const COUNT: usize = 3;
+
+    /// Returns the index of the run queue for this priority, starting with the highest one.
+    pub(crate) fn index(self) -> usize {
+        match self {
+            Priority::High => 0,
+            Priority::Normal => 1,
+            Priority::Low => 2,
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use once_cell::sync::Lazy;
+use slab::Slab;
+
+use crate::task::{Task, TaskId, TaskInfo, TaskLocalsWrapper};
+
+/// The number of shards the registry is split into.
+const SHARDS: usize = 16;
+
+/// The registry of live tasks.
+///
+/// Tasks are spread over several shards by ID, so that spawning and completing tasks on different
+/// threads rarely contends on the same lock.
+static REGISTRY: Lazy<Vec<Mutex<Slab<Arc<Record>>>>> =
+    Lazy::new(|| (0..SHARDS).map(|_| Mutex::new(Slab::new())).collect());
+
+/// What the registry knows about a live task.
+struct Record {
+    task: Task,
+    parent_id: Option<TaskId>,
+    spawned_at: Instant,
+    polls: AtomicUsize,
+}
+
+/// The entry of a task in the registry.
+///
+/// The task is removed from the registry when its entry is dropped.
+pub(crate) struct Entry {
+    shard: usize,
+    key: usize,
+    record: Arc<Record>,
+}
+
+impl Entry {
+    /// Registers a task spawned by the current task.
+    pub(crate) fn register(task: &Task) -> Entry {
+        let record = Arc::new(Record {
+            task: task.clone(),
+            parent_id: TaskLocalsWrapper::get_current(|t| t.id()),
+            spawned_at: Instant::now(),
+            polls: AtomicUsize::new(0),
+        });
+        let shard = task.id().0 % SHARDS;
+        let key = REGISTRY[shard].lock().unwrap().insert(record.clone());
+
+        Entry { shard, key, record }
+    }
+
+    /// Records that the task is being polled.
+    #[inline]
+    pub(crate) fn record_poll(&self) {
+        self.record.polls.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Drop for Entry {
+    fn drop(&mut self) {
+        REGISTRY[self.shard].lock().unwrap().remove(self.key);
+    }
+}
+
+/// Returns information about all live tasks, oldest first.
+pub(crate) fn snapshot() -> Vec<TaskInfo> {
+    let now = Instant::now();
+    let mut records: Vec<Arc<Record>> = Vec::new();
+    for shard in REGISTRY.iter() {
+        records.extend(shard.lock().unwrap().iter().map(|(_, r)| r.clone()));
+    }
+
+    let mut infos: Vec<TaskInfo> = records
+        .iter()
+        .map(|r| TaskInfo {
+            task: r.task.clone(),
+            parent_id: r.parent_id,
+            age: now.saturating_duration_since(r.spawned_at),
+            poll_count: r.polls.load(Ordering::Relaxed),
+        })
+        .collect();
+
+    infos.sort_by_key(|info| info.task.id().0);
+    infos
+}
@@ -0,0 +1,269 @@
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use crate::stream::Stream;
+use crate::sync::{channel, Receiver};
+use crate::task::{Builder, Context, Poll, Task, TaskLocalsWrapper, Waker};
+
+/// Runs a group of tasks that may borrow from the enclosing stack.
+///
+/// The closure receives a [`Scope`], which spawns child tasks with [`Scope::spawn`]. Unlike tasks
+/// spawned with [`task::spawn`], these children don't need to be `'static`: they can borrow
+/// anything that outlives the call to `scope`.
+///
+/// The future returned by `scope` waits for the future returned by the closure and for all the
+/// children to complete, and then returns the output of the closure. Dropping it cancels every
+/// child that is still running.
+///
+/// Each child is a separate task, with its own [`Task`] handle and task-local values. The
+/// children run concurrently with each other, but they are polled by the task awaiting the scope
+/// rather than by the runtime's workers, so they don't run in parallel. This is what makes
+/// borrowing sound: the scope's future can be leaked without being dropped, and a child running
+/// on another thread could then outlive what it borrows. Work that needs to run in parallel
+/// should be spawned with [`task::spawn`] instead. If a child panics, the panic propagates out
+/// of the scope and the other children are cancelled.
+///
+/// [`Scope`]: struct.Scope.html
+/// [`Scope::spawn`]: struct.Scope.html#method.spawn
+/// [`task::spawn`]: fn.spawn.html
+/// [`Task`]: struct.Task.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::task;
+///
+/// let data = vec![1, 2, 3, 4];
+/// let (left, right) = data.split_at(2);
+///
+/// let sum = task::scope(|s| async move {
+///     let a = s.spawn(async move { left.iter().sum::<i32>() });
+///     let b = s.spawn(async move { right.iter().sum::<i32>() });
+///     a.await + b.await
+/// })
+/// .await;
+///
+/// assert_eq!(sum, 10);
+/// #
+/// # })
+/// ```
+pub async fn scope<'env, F, Fut, T>(f: F) -> T
+where
+    F: FnOnce(Scope<'env>) -> Fut,
+    Fut: Future<Output = T> + 'env,
+{
+    let scope = Scope {
+        inner: Arc::new(Mutex::new(Inner {
+            children: Vec::new(),
+            waker: None,
+            closed: false,
+        })),
+    };
+    let future = Box::pin(f(scope.clone()));
+
+    Run {
+        scope,
+        future,
+        output: None,
+    }
+    .await
+}
+
+/// A scope for spawning tasks that may borrow from the enclosing stack.
+///
+/// Created by [`task::scope`]. It can be cloned to spawn tasks from within the children.
+///
+/// [`task::scope`]: fn.scope.html
+#[derive(Clone)]
+pub struct Scope<'env> {
+    inner: Arc<Mutex<Inner<'env>>>,
+}
+
+/// A child task of a scope.
+type Child<'env> = Pin<Box<dyn Future<Output = ()> + Send + 'env>>;
+
+struct Inner<'env> {
+    /// Children that have been spawned and have not completed yet.
+    children: Vec<Child<'env>>,
+
+    /// The waker of the task awaiting the scope.
+    waker: Option<Waker>,
+
+    /// Set once the scope has completed or been dropped.
+    closed: bool,
+}
+
+impl<'env> Scope<'env> {
+    /// Spawns a child task in the scope.
+    ///
+    /// The child can borrow anything that outlives the scope. It starts running the next time
+    /// the scope is polled.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the scope has already completed or been dropped, which can only
+    /// happen through a clone of the [`Scope`] that outlived it.
+    ///
+    /// [`Scope`]: struct.Scope.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::task;
+    ///
+    /// let mut results = vec![0; 3];
+    /// let slots = &mut results;
+    ///
+    /// task::scope(|s| async move {
+    ///     for (i, slot) in slots.into_iter().enumerate() {
+    ///         s.spawn(async move { *slot = i * 2 });
+    ///     }
+    /// })
+    /// .await;
+    ///
+    /// assert_eq!(results, [0, 2, 4]);
+    /// #
+    /// # })
+    /// ```
+    #[track_caller]
+    pub fn spawn<F, T>(&self, future: F) -> ScopedJoinHandle<T>
+    where
+        F: Future<Output = T> + Send + 'env,
+        T: Send + 'env,
+    {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.closed {
+            panic!("cannot spawn a task into a scope that has completed");
+        }
+
+        let (sender, receiver) = channel(1);
+        let wrapped = Builder::new().build(future);
+
+        kv_log_macro::trace!("spawn_scoped", {
+            task_id: wrapped.tag.id().0,
+            parent_task_id: TaskLocalsWrapper::get_current(|t| t.id().0).unwrap_or(0),
+            location: &*wrapped.tag.task().location().to_string(),
+        });
+
+        let task = wrapped.tag.task().clone();
+        let child = Box::pin(async move {
+            let _ = sender.try_send(wrapped.await);
+        });
+
+        inner.children.push(child);
+        if let Some(w) = inner.waker.take() {
+            w.wake();
+        }
+
+        ScopedJoinHandle { receiver, task }
+    }
+}
+
+impl fmt::Debug for Scope<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Scope { .. }")
+    }
+}
+
+/// A handle that awaits the result of a task spawned in a scope.
+///
+/// Dropping the handle doesn't affect the task, which keeps running until it completes or the
+/// scope is dropped.
+///
+/// Created by [`Scope::spawn`].
+///
+/// [`Scope::spawn`]: struct.Scope.html#method.spawn
+#[derive(Debug)]
+pub struct ScopedJoinHandle<T> {
+    receiver: Receiver<T>,
+    task: Task,
+}
+
+impl<T> ScopedJoinHandle<T> {
+    /// Returns the task handle of the child.
+    pub fn task(&self) -> &Task {
+        &self.task
+    }
+}
+
+impl<T> Future for ScopedJoinHandle<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.receiver).poll_next(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Some(val)) => Poll::Ready(val),
+            Poll::Ready(None) => panic!("cannot await the result of a cancelled task"),
+        }
+    }
+}
+
+/// The future that drives a scope and its children.
+struct Run<'env, Fut: Future> {
+    scope: Scope<'env>,
+    future: Pin<Box<Fut>>,
+    output: Option<Fut::Output>,
+}
+
+impl<Fut: Future> Unpin for Run<'_, Fut> {}
+
+impl<Fut: Future> Future for Run<'_, Fut> {
+    type Output = Fut::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        if this.output.is_none() {
+            if let Poll::Ready(val) = this.future.as_mut().poll(cx) {
+                this.output = Some(val);
+            }
+        }
+
+        // Poll the children, including the ones added while polling.
+        let mut pending = Vec::new();
+        loop {
+            let children = mem::take(&mut this.scope.inner.lock().unwrap().children);
+            if children.is_empty() {
+                break;
+            }
+            for mut child in children {
+                if child.as_mut().poll(cx).is_pending() {
+                    pending.push(child);
+                }
+            }
+        }
+
+        let mut inner = this.scope.inner.lock().unwrap();
+        inner.children.append(&mut pending);
+
+        if inner.children.is_empty() {
+            if let Some(val) = this.output.take() {
+                inner.closed = true;
+                return Poll::Ready(val);
+            }
+        }
+
+        // Children added from other threads need to wake the scope up.
+        inner.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<Fut: Future> Drop for Run<'_, Fut> {
+    fn drop(&mut self) {
+        // Children may hold clones of the scope, so drop them explicitly to cancel them.
+        let children = {
+            let mut inner = self.scope.inner.lock().unwrap();
+            inner.closed = true;
+            mem::take(&mut inner.children)
+        };
+        drop(children);
+    }
+}
@@ -8,6 +8,13 @@ use crate::task::{Builder, JoinHandle};
 ///
 /// [`std::thread`]: https://doc.rust-lang.org/std/thread/fn.spawn.html
 ///
+/// # Panics
+///
+/// This function panics if the runtime has been shut down. Use [`Builder::spawn`] to get an
+/// error instead.
+///
+/// [`Builder::spawn`]: struct.Builder.html#method.spawn
+///
 /// # Examples
 ///
 /// ```
@@ -19,10 +26,14 @@ use crate::task::{Builder, JoinHandle};
 ///     1 + 2
 /// });
 ///
+/// # #[cfg(not(feature = "unstable"))]
 /// assert_eq!(handle.await, 3);
+/// # #[cfg(feature = "unstable")]
+/// # assert_eq!(handle.await.unwrap(), 3);
 /// #
 /// # })
 /// ```
+#[track_caller]
 pub fn spawn<F, T>(future: F) -> JoinHandle<T>
 where
     F: Future<Output = T> + Send + 'static,
@@ -1,4 +1,4 @@
-use crate::task::{JoinHandle, Task};
+use crate::task::{JoinHandle, Output, Task, TaskLocalsWrapper};
 
 /// Spawns a blocking task.
 ///
@@ -6,6 +6,15 @@ use crate::task::{JoinHandle, Task};
 /// is useful to prevent long-running synchronous operations from blocking the main futures
 /// executor.
 ///
+/// The pool belongs to the runtime the current task runs on. It starts threads on demand, up to
+/// a limit, and tasks spawned while all of its threads are busy wait for one of them to become
+/// available. The limit can be configured with [`rt::Builder::max_blocking_threads`].
+///
+/// [`rt::Builder::max_blocking_threads`]: ../rt/struct.Builder.html#method.max_blocking_threads
+///
+/// The closure runs in the context of a new task, which inherits the inheritable task-locals of
+/// the current task.
+///
 /// See also: [`task::block_on`], [`task::spawn`].
 ///
 /// [`task::block_on`]: fn.block_on.html
@@ -24,19 +33,25 @@ use crate::task::{JoinHandle, Task};
 /// task::spawn_blocking(|| {
 ///     println!("long-running task here");
 /// })
-/// .await;
+/// .await
+/// .unwrap();
 /// #
 /// # })
 /// ```
 #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
 #[inline]
+#[track_caller]
 pub fn spawn_blocking<F, T>(f: F) -> JoinHandle<T>
 where
     F: FnOnce() -> T + Send + 'static,
     T: Send + 'static,
 {
-    once_cell::sync::Lazy::force(&crate::rt::RUNTIME);
-
-    let handle = smol::Task::blocking(async move { f() }).into();
-    JoinHandle::new(handle, Task::new(None))
+    let task = Task::new(None);
+    let tag = TaskLocalsWrapper::new(task.clone());
+    let output = Output::new(task.clone());
+    let slot = output.slot();
+    let handle = crate::rt::spawn_blocking(task.clone(), output, move || unsafe {
+        TaskLocalsWrapper::set_current(&tag, f)
+    });
+    JoinHandle::new(handle, slot, task)
 }
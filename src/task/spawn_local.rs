@@ -15,10 +15,11 @@ use crate::task::{Builder, JoinHandle};
 ///     1 + 2
 /// });
 ///
-/// assert_eq!(handle.await, 3);
+/// assert_eq!(handle.await.unwrap(), 3);
 /// #
 /// # })
 /// ```
+#[track_caller]
 pub fn spawn_local<F, T>(future: F) -> JoinHandle<T>
 where
     F: Future<Output = T> + 'static,
@@ -0,0 +1,171 @@
+use std::future::Future;
+use std::mem::ManuallyDrop;
+use std::pin::Pin;
+use std::thread::{self, ThreadId};
+
+use crate::task::{Builder, Context, JoinHandle, Poll};
+
+/// Spawns a task that stays on one of the runtime's worker threads.
+///
+/// The future doesn't need to be `Send`. Instead, `factory` is sent to a worker thread, where it
+/// is called to create the future, and the task is then only ever run by that worker. This makes
+/// it possible to hold thread-affine values, such as `Rc`s or handles of a library that is not
+/// thread-safe, across `.await`s. The result of the task must still be `Send`, so that it can be
+/// awaited from any thread.
+///
+/// Workers are picked in a round-robin fashion. Use [`Builder::spawn_on`] to pick one instead.
+///
+/// [`Builder::spawn_on`]: struct.Builder.html#method.spawn_on
+///
+/// # Panics
+///
+/// This function panics if the runtime has been shut down. Use [`Builder::spawn_pinned`] to get
+/// an error instead.
+///
+/// [`Builder::spawn_pinned`]: struct.Builder.html#method.spawn_pinned
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::rc::Rc;
+///
+/// use async_std::task;
+///
+/// let handle = task::spawn_pinned(|| async {
+///     let cache = Rc::new(vec![1, 2, 3]);
+///     task::yield_now().await;
+///     cache.iter().sum::<i32>()
+/// });
+///
+/// assert_eq!(handle.await.unwrap(), 6);
+/// #
+/// # })
+/// ```
+#[track_caller]
+pub fn spawn_pinned<F, Fut, T>(factory: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = T> + 'static,
+    T: Send + 'static,
+{
+    Builder::new()
+        .spawn_pinned(factory)
+        .expect("cannot spawn task")
+}
+
+/// A future that is created by a factory the first time it is polled, and is then only polled
+/// and dropped by the same thread.
+pub(crate) struct Pinned<F, Fut> {
+    factory: Option<F>,
+    local: Option<Local<Fut>>,
+}
+
+/// A future bound to the thread that created it.
+struct Local<Fut> {
+    thread: ThreadId,
+    future: ManuallyDrop<Pin<Box<Fut>>>,
+}
+
+// The future is only ever touched by the thread that created it, which is checked on every poll
+// and when it is dropped.
+unsafe impl<F: Send, Fut> Send for Pinned<F, Fut> {}
+
+impl<F, Fut> Pinned<F, Fut> {
+    pub(crate) fn new(factory: F) -> Pinned<F, Fut> {
+        Pinned {
+            factory: Some(factory),
+            local: None,
+        }
+    }
+}
+
+impl<F, Fut> Future for Pinned<F, Fut>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future,
+{
+    type Output = Fut::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        if let Some(factory) = this.factory.take() {
+            this.local = Some(Local {
+                thread: thread::current().id(),
+                future: ManuallyDrop::new(Box::pin(factory())),
+            });
+        }
+
+        let local = this.local.as_mut().unwrap();
+        assert!(
+            local.thread == thread::current().id(),
+            "pinned task polled by a thread that didn't create it"
+        );
+        local.future.as_mut().poll(cx)
+    }
+}
+
+impl<F, Fut> Unpin for Pinned<F, Fut> {}
+
+impl<Fut> Drop for Local<Fut> {
+    fn drop(&mut self) {
+        if self.thread == thread::current().id() {
+            unsafe { ManuallyDrop::drop(&mut self.future) }
+        } else {
+            // Leak the future rather than drop it on the wrong thread. This only happens if the
+            // task outlives its worker, so make it visible.
+            kv_log_macro::warn!("leaking a pinned task dropped off its thread", {
+                thread: &*format!("{:?}", self.thread),
+                current_thread: &*format!("{:?}", thread::current().id()),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    use futures::future;
+    use futures::task::{noop_waker, Context};
+
+    use super::Pinned;
+
+    #[test]
+    fn leaked_when_dropped_off_its_thread() {
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        struct Guard;
+
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        fn pending_pinned() -> impl Future<Output = ()> + Send + Unpin {
+            let mut pinned = Pinned::new(|| async {
+                let _guard = Guard;
+                future::pending::<()>().await
+            });
+            let waker = noop_waker();
+            let mut cx = Context::from_waker(&waker);
+            assert!(Pin::new(&mut pinned).poll(&mut cx).is_pending());
+            pinned
+        }
+
+        // Dropped on the thread that created it, the future is dropped.
+        drop(pending_pinned());
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+
+        // Dropped on another thread, the future is leaked instead.
+        let pinned = pending_pinned();
+        thread::spawn(move || drop(pinned)).join().unwrap();
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+    }
+}
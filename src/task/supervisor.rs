@@ -0,0 +1,247 @@
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+use crate::io;
+use crate::task::{self, Builder, JoinError, JoinHandle, JoinSet};
+
+/// Restarts tasks that fail.
+///
+/// A supervisor spawns tasks from a factory, and whenever a task returns an error or panics, it
+/// calls the factory again to start a fresh one. Each task spawned by the supervisor is restarted
+/// on its own, without affecting the others (a one-for-one strategy).
+///
+/// Restarts can be delayed with an exponential [`backoff`]. A task that keeps failing is given up
+/// on once it has been restarted a [maximum number of times] within a time window, in which case
+/// the failure of its last run is returned. By default, a task is restarted
+/// immediately, at most 3 times within 5 seconds.
+///
+/// [`backoff`]: #method.backoff
+/// [maximum number of times]: #method.max_restarts
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// use async_std::task::Supervisor;
+///
+/// let runs = Arc::new(AtomicUsize::new(0));
+///
+/// let r = runs.clone();
+/// let handle = Supervisor::new()
+///     .name("flaky".to_string())
+///     .backoff(Duration::from_millis(1), Duration::from_millis(10))
+///     .spawn(move || {
+///         let r = r.clone();
+///         async move {
+///             if r.fetch_add(1, Ordering::SeqCst) < 2 {
+///                 return Err("not yet");
+///             }
+///             Ok("done")
+///         }
+///     });
+///
+/// assert_eq!(handle.await.unwrap().unwrap(), "done");
+/// assert_eq!(runs.load(Ordering::SeqCst), 3);
+/// #
+/// # })
+/// ```
+#[derive(Clone, Debug)]
+pub struct Supervisor {
+    name: Option<String>,
+    backoff: Option<(Duration, Duration)>,
+    max_restarts: usize,
+    window: Duration,
+}
+
+impl Supervisor {
+    /// Creates a new supervisor with the default configuration.
+    pub fn new() -> Supervisor {
+        Supervisor {
+            name: None,
+            backoff: None,
+            max_restarts: 3,
+            window: Duration::from_secs(5),
+        }
+    }
+
+    /// Configures the name of the supervised tasks.
+    ///
+    /// Every run of a task is spawned with this name, as with [`Builder::name`].
+    ///
+    /// [`Builder::name`]: struct.Builder.html#method.name
+    pub fn name(mut self, name: String) -> Supervisor {
+        self.name = Some(name);
+        self
+    }
+
+    /// Delays restarts with an exponential backoff.
+    ///
+    /// The first restart within the restart window is delayed by `initial`, and every following
+    /// one by twice the previous delay, up to `max`.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Supervisor {
+        self.backoff = Some((initial, max));
+        self
+    }
+
+    /// Gives up on a task once it has been restarted `max` times within `window`.
+    ///
+    /// With a `max` of zero, tasks are never restarted.
+    pub fn max_restarts(mut self, max: usize, window: Duration) -> Supervisor {
+        self.max_restarts = max;
+        self.window = window;
+        self
+    }
+
+    /// Spawns a task that is restarted whenever it fails.
+    ///
+    /// The `factory` is called to create the future of every run. The returned handle completes
+    /// with the output of the first run that succeeds, or with the failure of the last run once
+    /// the supervisor gives up.
+    ///
+    /// Cancelling the returned handle also cancels the task that is currently running. A run
+    /// that gets cancelled, for example because its runtime is shut down, is not restarted.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the task can't be spawned, like [`task::spawn`]. If a restart can't
+    /// be spawned, the handle completes with [`SupervisorError::Spawn`] instead.
+    ///
+    /// [`task::spawn`]: fn.spawn.html
+    /// [`SupervisorError::Spawn`]: enum.SupervisorError.html#variant.Spawn
+    #[track_caller]
+    pub fn spawn<F, Fut, T, E>(&self, factory: F) -> JoinHandle<Result<T, SupervisorError<E>>>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Send + 'static,
+        E: Send + 'static,
+    {
+        task::spawn(self.clone().supervise(factory))
+    }
+
+    /// Runs the task until it succeeds or the supervisor gives up on it.
+    async fn supervise<F, Fut, T, E>(self, mut factory: F) -> Result<T, SupervisorError<E>>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Send + 'static,
+        E: Send + 'static,
+    {
+        let mut restarts = VecDeque::new();
+
+        loop {
+            let mut builder = Builder::new();
+            if let Some(name) = &self.name {
+                builder = builder.name(name.clone());
+            }
+            let handle = match builder.spawn(factory()) {
+                Ok(handle) => handle,
+                Err(err) => return Err(SupervisorError::Spawn(err)),
+            };
+
+            // Keep the run in a set, so that it gets cancelled along with the supervisor.
+            let mut run = JoinSet::new();
+            run.push(handle);
+            let err = match run.join_next().await.unwrap() {
+                Ok(Ok(val)) => return Ok(val),
+                Ok(Err(err)) => SupervisorError::Error(err),
+                Err(err) if err.is_cancelled() => return Err(SupervisorError::Join(err)),
+                Err(err) => SupervisorError::Join(err),
+            };
+
+            // Forget the restarts that are outside of the window.
+            let now = crate::rt::now();
+            while let Some(&at) = restarts.front() {
+                if now.saturating_duration_since(at) < self.window {
+                    break;
+                }
+                restarts.pop_front();
+            }
+
+            if restarts.len() >= self.max_restarts {
+                return Err(err);
+            }
+            restarts.push_back(now);
+
+            kv_log_macro::warn!("restarting task", {
+                task_name: self.name.as_deref().unwrap_or(""),
+                restarts: restarts.len() as u64,
+            });
+
+            if let Some((initial, max)) = self.backoff {
+                task::sleep(backoff_delay(initial, max, restarts.len())).await;
+            }
+        }
+    }
+}
+
+impl Default for Supervisor {
+    fn default() -> Supervisor {
+        Supervisor::new()
+    }
+}
+
+/// Returns the delay before the `n`-th restart within the window, starting from one.
+fn backoff_delay(initial: Duration, max: Duration, n: usize) -> Duration {
+    let shift = u32::try_from(n.saturating_sub(1)).unwrap_or(u32::MAX);
+    let factor = 1u32.checked_shl(shift).unwrap_or(u32::MAX);
+    initial
+        .checked_mul(factor)
+        .map_or(max, |delay| delay.min(max))
+}
+
+/// The failure of a supervised task that the [`Supervisor`] gave up on.
+///
+/// [`Supervisor`]: struct.Supervisor.html
+#[derive(Debug)]
+pub enum SupervisorError<E> {
+    /// The task returned an error.
+    Error(E),
+
+    /// The task panicked or was cancelled.
+    Join(JoinError),
+
+    /// The task couldn't be spawned, for example because its runtime was shut down.
+    Spawn(io::Error),
+}
+
+impl<E: fmt::Display> fmt::Display for SupervisorError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SupervisorError::Error(err) => write!(f, "task failed: {}", err),
+            SupervisorError::Join(err) => fmt::Display::fmt(err, f),
+            SupervisorError::Spawn(err) => write!(f, "cannot spawn task: {}", err),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> Error for SupervisorError<E> {}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::backoff_delay;
+
+    #[test]
+    fn backoff_delay_doubles_up_to_max() {
+        let initial = Duration::from_millis(10);
+        let max = Duration::from_secs(1);
+
+        assert_eq!(backoff_delay(initial, max, 0), initial);
+        assert_eq!(backoff_delay(initial, max, 1), initial);
+        assert_eq!(backoff_delay(initial, max, 2), initial * 2);
+        assert_eq!(backoff_delay(initial, max, 8), max);
+        assert_eq!(backoff_delay(initial, max, 33), max);
+        assert_eq!(backoff_delay(initial, max, usize::MAX), max);
+    }
+}
@@ -1,4 +1,8 @@
+#[cfg(all(feature = "unstable", feature = "backtrace"))]
+use std::backtrace::Backtrace;
 use std::fmt;
+use std::panic::Location;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use crate::task::TaskId;
@@ -11,18 +15,44 @@ pub struct Task {
 
     /// The optional task name.
     name: Option<Arc<String>>,
+
+    /// The location of the code that spawned the task.
+    location: &'static Location<'static>,
+
+    /// The backtrace of the code that spawned the task.
+    #[cfg(all(feature = "unstable", feature = "backtrace"))]
+    backtrace: Arc<Backtrace>,
+
+    /// Set once the task's output has been stored and its future dropped.
+    finished: Arc<AtomicBool>,
 }
 
 impl Task {
-    /// Creates a new task handle.
+    /// Creates a new task handle spawned at the caller's location.
     #[inline]
+    #[track_caller]
     pub(crate) fn new(name: Option<Arc<String>>) -> Task {
         Task {
             id: TaskId::generate(),
             name,
+            location: Location::caller(),
+            #[cfg(all(feature = "unstable", feature = "backtrace"))]
+            backtrace: Arc::new(Backtrace::force_capture()),
+            finished: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Returns `true` if the task has stored its output, if any, and dropped its future.
+    #[cfg_attr(all(target_arch = "wasm32", not(feature = "unstable")), allow(dead_code))]
+    pub(crate) fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Acquire)
+    }
+
+    /// Records that the task has stored its output, if any, and dropped its future.
+    pub(crate) fn finish(&self) {
+        self.finished.store(true, Ordering::Release);
+    }
+
     /// Gets the task's unique identifier.
     #[inline]
     pub fn id(&self) -> TaskId {
@@ -37,6 +67,49 @@ impl Task {
     pub fn name(&self) -> Option<&str> {
         self.name.as_ref().map(|s| s.as_str())
     }
+
+    /// Returns the location of the code that spawned this task.
+    ///
+    /// This is the call to [`task::spawn`], [`Builder::spawn`] or any of their siblings, or the
+    /// call of a function marked `#[track_caller]` that spawned the task on behalf of its caller.
+    ///
+    /// [`task::spawn`]: fn.spawn.html
+    /// [`Builder::spawn`]: struct.Builder.html#method.spawn
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::task;
+    ///
+    /// let handle = task::spawn(async {});
+    /// assert_eq!(handle.task().location().line(), line!() - 1);
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Returns the location of the code that spawned this task.
+    #[cfg(not(feature = "unstable"))]
+    pub(crate) fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Returns the backtrace of the code that spawned this task.
+    ///
+    /// Capturing a backtrace is expensive, so it is only done when the `backtrace` Cargo feature
+    /// is enabled, in which case it is captured for every task regardless of the
+    /// `RUST_BACKTRACE` environment variable.
+    #[cfg(all(feature = "unstable", feature = "backtrace"))]
+    #[cfg_attr(feature = "docs", doc(cfg(all(unstable, backtrace))))]
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.backtrace
+    }
 }
 
 impl fmt::Debug for Task {
@@ -44,6 +117,7 @@ impl fmt::Debug for Task {
         f.debug_struct("Task")
             .field("id", &self.id())
             .field("name", &self.name())
+            .field("location", &self.location)
             .finish()
     }
 }
@@ -0,0 +1,90 @@
+use std::fmt;
+use std::panic::Location;
+use std::time::Duration;
+
+use crate::task::{Task, TaskId};
+
+/// Information about a live task, as returned by [`task::dump`].
+///
+/// [`task::dump`]: fn.dump.html
+#[derive(Clone)]
+pub struct TaskInfo {
+    pub(crate) task: Task,
+    pub(crate) parent_id: Option<TaskId>,
+    pub(crate) age: Duration,
+    pub(crate) poll_count: usize,
+}
+
+impl TaskInfo {
+    /// Returns a handle to the task.
+    pub fn task(&self) -> &Task {
+        &self.task
+    }
+
+    /// Gets the task's unique identifier.
+    pub fn id(&self) -> TaskId {
+        self.task.id()
+    }
+
+    /// Returns the name of the task.
+    pub fn name(&self) -> Option<&str> {
+        self.task.name()
+    }
+
+    /// Returns the identifier of the task that spawned this task.
+    ///
+    /// This is `None` if the task was spawned from outside of any task.
+    pub fn parent_id(&self) -> Option<TaskId> {
+        self.parent_id
+    }
+
+    /// Returns the location of the code that spawned the task.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.task.location()
+    }
+
+    /// Returns how long ago the task was spawned.
+    pub fn age(&self) -> Duration {
+        self.age
+    }
+
+    /// Returns how many times the task has been polled.
+    ///
+    /// A task that has been alive for a long time but is never polled is waiting for an event
+    /// that doesn't happen, while a task whose poll count keeps growing is making progress.
+    pub fn poll_count(&self) -> usize {
+        self.poll_count
+    }
+}
+
+impl fmt::Debug for TaskInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskInfo")
+            .field("id", &self.id())
+            .field("name", &self.name())
+            .field("parent_id", &self.parent_id)
+            .field("location", &self.location())
+            .field("age", &self.age)
+            .field("poll_count", &self.poll_count)
+            .finish()
+    }
+}
+
+impl fmt::Display for TaskInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "task {}", self.id())?;
+        if let Some(name) = self.name() {
+            write!(f, " {:?}", name)?;
+        }
+        if let Some(parent_id) = self.parent_id {
+            write!(f, " (parent {})", parent_id)?;
+        }
+        write!(
+            f,
+            " spawned at {}, alive for {:?}, polled {} times",
+            self.location(),
+            self.age,
+            self.poll_count
+        )
+    }
+}
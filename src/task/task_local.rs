@@ -1,19 +1,38 @@
 use std::cell::UnsafeCell;
 use std::error::Error;
 use std::fmt;
+#[cfg(feature = "unstable")]
+use std::future::Future;
+#[cfg(feature = "unstable")]
+use std::mem;
+#[cfg(feature = "unstable")]
+use std::pin::Pin;
 use std::sync::atomic::{AtomicU32, Ordering};
 
+#[cfg(feature = "unstable")]
+use pin_project_lite::pin_project;
+
 use crate::task::TaskLocalsWrapper;
+#[cfg(feature = "unstable")]
+use crate::task::{Context, Poll};
 
 /// The key for accessing a task-local value.
 ///
 /// Every task-local value is lazily initialized on first access and destroyed when the task
 /// completes.
+#[cfg_attr(
+    feature = "unstable",
+    doc = "Values declared with `#[inherit]` are instead initialized with a clone of the spawning \
+           task's value, if it has one."
+)]
 #[derive(Debug)]
 pub struct LocalKey<T: Send + 'static> {
     #[doc(hidden)]
     pub __init: fn() -> T,
 
+    #[doc(hidden)]
+    pub __inherit: Option<fn(&T) -> T>,
+
     #[doc(hidden)]
     pub __key: AtomicU32,
 }
@@ -102,9 +121,16 @@ impl<T: Send + 'static> LocalKey<T> {
             // Prepare the numeric key, initialization function, and the map of task-locals.
             let key = self.key();
             let init = || Box::new((self.__init)()) as Box<dyn Send>;
+            let inherit = self.inherit();
 
             // Get the value in the map of task-locals, or initialize and insert one.
-            let value: *const dyn Send = task.locals().get_or_insert(key, init);
+            let locals = task.locals();
+            let value: *const dyn Send = locals.get_or_insert(key, inherit, init);
+
+            // Make sure the value doesn't get replaced while the closure holds a reference to it.
+            // Values can only be replaced with unstable methods.
+            #[cfg(feature = "unstable")]
+            let _borrow = locals.borrow(key);
 
             // Call the closure with the value passed as an argument.
             f(&*(value as *const T))
@@ -112,6 +138,135 @@ impl<T: Send + 'static> LocalKey<T> {
         .ok_or(AccessError { _private: () })
     }
 
+    /// Sets the task-local value with this key.
+    ///
+    /// The previous value, if any, is dropped.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if not called within the context of a task, or if called from
+    /// within [`with`] on the same key.
+    ///
+    /// [`with`]: #method.with
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #
+    /// use async_std::task;
+    /// use async_std::prelude::*;
+    ///
+    /// task_local! {
+    ///     static NAME: String = String::new();
+    /// }
+    ///
+    /// task::block_on(async {
+    ///     NAME.set("worker".to_string());
+    ///     assert_eq!(NAME.with(|n| n.clone()), "worker");
+    /// });
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn set(&'static self, value: T) {
+        let old = self
+            .swap(Some(value))
+            .expect("`LocalKey::set` called outside the context of a task");
+        drop(old);
+    }
+
+    /// Replaces the task-local value with this key, returning the previous value.
+    ///
+    /// If the value hasn't been initialized in this task yet, it is initialized first, so the
+    /// initial value is returned.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if not called within the context of a task, or if called from
+    /// within [`with`] on the same key.
+    ///
+    /// [`with`]: #method.with
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #
+    /// use async_std::task;
+    /// use async_std::prelude::*;
+    ///
+    /// task_local! {
+    ///     static COUNT: u32 = 1;
+    /// }
+    ///
+    /// task::block_on(async {
+    ///     assert_eq!(COUNT.replace(2), 1);
+    ///     assert_eq!(COUNT.replace(3), 2);
+    /// });
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn replace(&'static self, value: T) -> T {
+        self.swap(Some(value))
+            .expect("`LocalKey::replace` called outside the context of a task")
+            .unwrap_or_else(self.__init)
+    }
+
+    /// Sets the task-local value with this key while a future is being polled.
+    ///
+    /// Every time the returned future is polled, the value is put in place for the duration of
+    /// the poll, and the task's own value, if any, is restored afterwards. Changes made to the
+    /// value from within the future are kept for the next poll.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #
+    /// use async_std::task;
+    /// use async_std::prelude::*;
+    ///
+    /// task_local! {
+    ///     static DEPTH: u32 = 0;
+    /// }
+    ///
+    /// task::block_on(async {
+    ///     let inner = DEPTH.scope(1, async {
+    ///         task::yield_now().await;
+    ///         DEPTH.with(|d| *d)
+    ///     });
+    ///
+    ///     assert_eq!(inner.await, 1);
+    ///     assert_eq!(DEPTH.with(|d| *d), 0);
+    /// });
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn scope<F>(&'static self, value: T, future: F) -> impl Future<Output = F::Output>
+    where
+        F: Future,
+    {
+        ScopeFuture {
+            key: self,
+            value: Some(value),
+            future,
+        }
+    }
+
+    /// Replaces the task-local value with this key, removing it if `value` is `None`.
+    ///
+    /// Returns `None` if not called within the context of a task.
+    #[cfg(feature = "unstable")]
+    fn swap(&'static self, value: Option<T>) -> Option<Option<T>> {
+        TaskLocalsWrapper::get_current(|task| {
+            let value = value.map(|v| Box::new(v) as Box<dyn Send>);
+            let old = task.locals().replace(self.key(), self.inherit(), value);
+            old.map(|old| unsafe { *Box::from_raw(Box::into_raw(old) as *mut T) })
+        })
+    }
+
+    /// Returns the key as an `Inherit` object if its values are inheritable.
+    fn inherit(&'static self) -> Option<&'static dyn Inherit> {
+        self.__inherit.map(|_| self as &'static dyn Inherit)
+    }
+
     /// Returns the numeric key associated with this task-local.
     #[inline]
     fn key(&self) -> u32 {
@@ -137,6 +292,57 @@ impl<T: Send + 'static> LocalKey<T> {
     }
 }
 
+#[cfg(feature = "unstable")]
+pin_project! {
+    /// A future that sets a task-local value while it is being polled.
+    struct ScopeFuture<T, F>
+    where
+        T: Send,
+        T: 'static,
+    {
+        key: &'static LocalKey<T>,
+        value: Option<T>,
+        #[pin]
+        future: F,
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: Send + 'static, F: Future> Future for ScopeFuture<T, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let key: &'static LocalKey<T> = this.key;
+        let value = this.value;
+
+        let prev = key
+            .swap(value.take())
+            .expect("`LocalKey::scope` polled outside the context of a task");
+        defer! {
+            *value = key.swap(prev).unwrap();
+        }
+
+        this.future.poll(cx)
+    }
+}
+
+/// Clones task-local values of a key into new tasks.
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+pub(crate) trait Inherit: Sync {
+    /// Clones a value of this key.
+    ///
+    /// The value must be of the key's type.
+    unsafe fn inherit(&self, value: *const dyn Send) -> Box<dyn Send>;
+}
+
+impl<T: Send + 'static> Inherit for LocalKey<T> {
+    unsafe fn inherit(&self, value: *const dyn Send) -> Box<dyn Send> {
+        let inherit = self.__inherit.unwrap();
+        Box::new(inherit(&*(value as *const T)))
+    }
+}
+
 /// An error returned by [`LocalKey::try_with`].
 ///
 /// [`LocalKey::try_with`]: struct.LocalKey.html#method.try_with
@@ -164,6 +370,14 @@ struct Entry {
     /// Key identifying the task-local variable.
     key: u32,
 
+    /// Clones the value into new tasks, if it is inheritable.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    inherit: Option<&'static dyn Inherit>,
+
+    /// The number of references to the value that are currently handed out.
+    #[cfg(feature = "unstable")]
+    borrows: usize,
+
     /// Value stored in this entry.
     value: Box<dyn Send>,
 }
@@ -182,9 +396,55 @@ impl LocalsMap {
         }
     }
 
+    /// Creates a map holding clones of the inheritable task-locals in this map.
+    #[cfg(feature = "unstable")]
+    pub fn inherit(&self) -> LocalsMap {
+        // Values may access task-locals while being cloned, so don't hold a reference to the
+        // entries then.
+        let inheritable: Vec<_> = match unsafe { (*self.entries.get()).as_ref() } {
+            None => Vec::new(),
+            Some(entries) => entries
+                .iter()
+                .filter_map(|e| {
+                    e.inherit
+                        .map(|inherit| (e.key, inherit, &*e.value as *const dyn Send))
+                })
+                .collect(),
+        };
+
+        // Make sure the values don't get replaced while they are being cloned.
+        #[cfg(feature = "unstable")]
+        let _borrows: Vec<_> = inheritable
+            .iter()
+            .map(|(key, _, _)| self.borrow(*key))
+            .collect();
+
+        let entries = inheritable
+            .iter()
+            .copied()
+            .map(|(key, inherit, value)| Entry {
+                key,
+                inherit: Some(inherit),
+                #[cfg(feature = "unstable")]
+                borrows: 0,
+                // Values are boxed, so they don't move when new entries are inserted.
+                value: unsafe { inherit.inherit(value) },
+            })
+            .collect();
+
+        LocalsMap {
+            entries: UnsafeCell::new(Some(entries)),
+        }
+    }
+
     /// Returns a task-local value associated with `key` or inserts one constructed by `init`.
     #[inline]
-    pub fn get_or_insert(&self, key: u32, init: impl FnOnce() -> Box<dyn Send>) -> &dyn Send {
+    pub fn get_or_insert(
+        &self,
+        key: u32,
+        inherit: Option<&'static dyn Inherit>,
+        init: impl FnOnce() -> Box<dyn Send>,
+    ) -> &dyn Send {
         match unsafe { (*self.entries.get()).as_mut() } {
             None => panic!("can't access task-locals while the task is being dropped"),
             Some(entries) => {
@@ -192,7 +452,16 @@ impl LocalsMap {
                     Ok(i) => i,
                     Err(i) => {
                         let value = init();
-                        entries.insert(i, Entry { key, value });
+                        entries.insert(
+                            i,
+                            Entry {
+                                key,
+                                inherit,
+                                #[cfg(feature = "unstable")]
+                                borrows: 0,
+                                value,
+                            },
+                        );
                         i
                     }
                 };
@@ -201,6 +470,64 @@ impl LocalsMap {
         }
     }
 
+    /// Replaces the task-local value associated with `key`, removing it if `value` is `None`.
+    ///
+    /// Returns the previous value, which the caller should drop once the map isn't borrowed.
+    #[cfg(feature = "unstable")]
+    pub fn replace(
+        &self,
+        key: u32,
+        inherit: Option<&'static dyn Inherit>,
+        value: Option<Box<dyn Send>>,
+    ) -> Option<Box<dyn Send>> {
+        match unsafe { (*self.entries.get()).as_mut() } {
+            None => panic!("can't access task-locals while the task is being dropped"),
+            Some(entries) => match entries.binary_search_by_key(&key, |e| e.key) {
+                Ok(i) => {
+                    if entries[i].borrows > 0 {
+                        panic!("can't replace a task-local value while it is borrowed");
+                    }
+                    match value {
+                        Some(value) => Some(mem::replace(&mut entries[i].value, value)),
+                        None => Some(entries.remove(i).value),
+                    }
+                }
+                Err(i) => {
+                    if let Some(value) = value {
+                        entries.insert(
+                            i,
+                            Entry {
+                                key,
+                                inherit,
+                                borrows: 0,
+                                value,
+                            },
+                        );
+                    }
+                    None
+                }
+            },
+        }
+    }
+
+    /// Marks the task-local value associated with `key` as borrowed until the returned guard is
+    /// dropped.
+    #[cfg(feature = "unstable")]
+    fn borrow(&self, key: u32) -> Borrow<'_> {
+        self.update(key, |entry| entry.borrows += 1);
+        Borrow { map: self, key }
+    }
+
+    /// Updates the entry for `key`, if any.
+    #[cfg(feature = "unstable")]
+    fn update(&self, key: u32, f: impl FnOnce(&mut Entry)) {
+        if let Some(entries) = unsafe { (*self.entries.get()).as_mut() } {
+            if let Ok(i) = entries.binary_search_by_key(&key, |e| e.key) {
+                f(&mut entries[i]);
+            }
+        }
+    }
+
     /// Clears the map and drops all task-locals.
     ///
     /// This method is only safe to call at the end of the task.
@@ -211,3 +538,17 @@ impl LocalsMap {
         drop(entries);
     }
 }
+
+/// A borrow of a task-local value, released when dropped.
+#[cfg(feature = "unstable")]
+struct Borrow<'a> {
+    map: &'a LocalsMap,
+    key: u32,
+}
+
+#[cfg(feature = "unstable")]
+impl Drop for Borrow<'_> {
+    fn drop(&mut self) {
+        self.map.update(self.key, |entry| entry.borrows -= 1);
+    }
+}
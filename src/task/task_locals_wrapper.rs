@@ -1,12 +1,16 @@
 use std::cell::Cell;
 use std::ptr;
 
+#[cfg(all(feature = "unstable", not(target_os = "unknown")))]
+use crate::task::registry;
+#[cfg(feature = "unstable")]
+use crate::task::CancellationToken;
 use crate::task::{LocalsMap, Task, TaskId};
 use crate::utils::abort_on_panic;
 
 thread_local! {
     /// A pointer to the currently running task.
-    static CURRENT: Cell<*const TaskLocalsWrapper> = Cell::new(ptr::null_mut());
+    static CURRENT: Cell<*const TaskLocalsWrapper> = const { Cell::new(ptr::null_mut()) };
 }
 
 /// A wrapper to store task local data.
@@ -16,19 +20,48 @@ pub(crate) struct TaskLocalsWrapper {
 
     /// The map holding task-local values.
     locals: LocalsMap,
+
+    /// The entry of the task in the registry of live tasks.
+    #[cfg(all(feature = "unstable", not(target_os = "unknown")))]
+    entry: registry::Entry,
+
+    /// The cancellation token attached to the task.
+    #[cfg(feature = "unstable")]
+    cancellation_token: Option<CancellationToken>,
 }
 
 impl TaskLocalsWrapper {
     /// Creates a new task handle.
     ///
     /// If the task is unnamed, the inner representation of the task will be lazily allocated on
-    /// demand.
+    /// demand. Inheritable task-locals of the current task are cloned into the new one, and if
+    /// the current task has a cancellation token, the new one gets a child token of it.
     #[inline]
     pub(crate) fn new(task: Task) -> Self {
         Self {
+            #[cfg(all(feature = "unstable", not(target_os = "unknown")))]
+            entry: registry::Entry::register(&task),
             task,
+            #[cfg(feature = "unstable")]
+            locals: TaskLocalsWrapper::get_current(|t| t.locals().inherit())
+                .unwrap_or_else(LocalsMap::new),
+            #[cfg(not(feature = "unstable"))]
             locals: LocalsMap::new(),
+            #[cfg(feature = "unstable")]
+            cancellation_token: TaskLocalsWrapper::get_current(|t| {
+                t.cancellation_token().map(CancellationToken::child_token)
+            })
+            .unwrap_or(None),
+        }
+    }
+
+    /// Attaches a cancellation token to the task, in place of the inherited one.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn with_cancellation_token(mut self, token: Option<CancellationToken>) -> Self {
+        if token.is_some() {
+            self.cancellation_token = token;
         }
+        self
     }
 
     /// Gets the task's unique identifier.
@@ -47,6 +80,19 @@ impl TaskLocalsWrapper {
         &self.locals
     }
 
+    /// Returns the cancellation token attached to the task.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation_token.as_ref()
+    }
+
+    /// Records that the task is being polled.
+    #[inline]
+    pub(crate) fn record_poll(&self) {
+        #[cfg(all(feature = "unstable", not(target_os = "unknown")))]
+        self.entry.record_poll();
+    }
+
     /// Set a reference to the current task.
     pub(crate) unsafe fn set_current<F, R>(task: *const TaskLocalsWrapper, f: F) -> R
     where
@@ -80,5 +126,10 @@ impl Drop for TaskLocalsWrapper {
         abort_on_panic(|| {
             unsafe { self.locals.clear() };
         });
+
+        // On wasm, the wrapper is dropped after the task has sent its output. Other targets mark
+        // the task finished when its `Output` is dropped.
+        #[cfg(target_arch = "wasm32")]
+        self.task.finish();
     }
 }
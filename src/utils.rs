@@ -20,22 +20,34 @@ pub fn abort_on_panic<T>(f: impl FnOnce() -> T) -> T {
     t
 }
 
-/// Generates a random number in `0..n`.
+/// Returns the message of a panic payload, or `"Box<Any>"` if it isn't a string.
+#[cfg(feature = "default")]
+#[cfg_attr(all(target_os = "unknown", not(feature = "unstable")), allow(dead_code))]
+pub(crate) fn panic_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "Box<Any>"
+    }
+}
+
 #[cfg(feature = "unstable")]
-pub fn random(n: u32) -> u32 {
-    use std::cell::Cell;
-    use std::num::Wrapping;
-
-    thread_local! {
-        static RNG: Cell<Wrapping<u32>> = {
-            // Take the address of a local value as seed.
-            let mut x = 0i32;
-            let r = &mut x;
-            let addr = r as *mut i32 as usize;
-            Cell::new(Wrapping(addr as u32))
-        }
+thread_local! {
+    /// The state of the random number generator of the current thread.
+    static RNG: std::cell::Cell<std::num::Wrapping<u32>> = {
+        // Take the address of a local value as seed.
+        let mut x = 0i32;
+        let r = &mut x;
+        let addr = r as *mut i32 as usize;
+        std::cell::Cell::new(std::num::Wrapping(addr as u32))
     }
+}
 
+/// Generates a random number in `0..n`.
+#[cfg(feature = "unstable")]
+pub fn random(n: u32) -> u32 {
     RNG.with(|rng| {
         // This is the 32-bit variant of Xorshift.
         //
@@ -54,13 +66,20 @@ pub fn random(n: u32) -> u32 {
     })
 }
 
+/// Seeds the random number generator of the current thread, making `random` reproducible.
+#[cfg(feature = "unstable")]
+pub(crate) fn seed_random(seed: u32) {
+    // Xorshift gets stuck at zero.
+    RNG.with(|rng| rng.set(std::num::Wrapping(seed | 1)));
+}
+
 /// Add additional context to errors
 pub(crate) trait Context {
     fn context(self, message: impl Fn() -> String) -> Self;
 }
 
 #[cfg(all(not(target_os = "unknown"), feature = "default"))]
-pub(crate) type Timer = smol::Timer;
+pub(crate) use crate::rt::Timer;
 
 #[cfg(all(target_arch = "wasm32", feature = "default"))]
 #[derive(Debug)]
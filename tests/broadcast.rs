@@ -0,0 +1,144 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::future;
+use async_std::sync::{broadcast, BroadcastRecvError, BroadcastTryRecvError};
+use async_std::task;
+
+#[cfg(not(target_os = "unknown"))]
+use async_std::task::spawn;
+#[cfg(target_os = "unknown")]
+use async_std::task::spawn_local as spawn;
+
+#[cfg(target_arch = "wasm32")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn every_receiver_sees_every_message() {
+    task::block_on(async {
+        let (s, r) = broadcast(10);
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let mut r = r.clone();
+                spawn(async move {
+                    let mut sum = 0;
+                    while let Ok(i) = r.recv().await {
+                        sum += i;
+                    }
+                    sum
+                })
+            })
+            .collect();
+        drop(r);
+
+        for i in 1..=10 {
+            assert_eq!(s.send(i), Ok(4));
+            task::sleep(ms(1)).await;
+        }
+        drop(s);
+
+        for handle in handles {
+            assert_eq!(handle.await.unwrap(), 55);
+        }
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn lagged() {
+    task::block_on(async {
+        let (s, mut slow) = broadcast(3);
+        let mut fast = s.subscribe();
+
+        for i in 0..3 {
+            s.send(i).unwrap();
+            assert_eq!(fast.recv().await, Ok(i));
+        }
+
+        // The sender doesn't wait for the slow receiver.
+        for i in 3..5 {
+            s.send(i).unwrap();
+        }
+        assert_eq!(slow.recv().await, Err(BroadcastRecvError::Lagged(2)));
+        assert_eq!(slow.recv().await, Ok(2));
+        assert_eq!(fast.recv().await, Ok(3));
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn subscribe() {
+    task::block_on(async {
+        let (s, r) = broadcast(4);
+        s.send(1).unwrap();
+
+        let mut late = s.subscribe();
+        assert_eq!(s.receiver_count(), 2);
+        s.send(2).unwrap();
+        assert_eq!(late.recv().await, Ok(2));
+        assert_eq!(late.try_recv(), Err(BroadcastTryRecvError::Empty));
+
+        drop(r);
+        drop(late);
+        assert_eq!(s.receiver_count(), 0);
+        assert_eq!(s.send(3), Err(3));
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn closed() {
+    task::block_on(async {
+        let (s, mut r) = broadcast::<i32>(1);
+
+        assert!(future::timeout(ms(10), r.recv()).await.is_err());
+
+        let s2 = s.clone();
+        spawn(async move {
+            task::sleep(ms(10)).await;
+            drop(s);
+            s2.send(1).unwrap();
+        });
+
+        assert_eq!(r.recv().await, Ok(1));
+        assert_eq!(r.recv().await, Err(BroadcastRecvError::Closed));
+        assert_eq!(r.try_recv(), Err(BroadcastTryRecvError::Closed));
+    })
+}
+
+#[test]
+fn panicking_clone() {
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static PANIC: AtomicBool = AtomicBool::new(true);
+
+    #[derive(Debug, PartialEq)]
+    struct Msg(i32);
+
+    impl Clone for Msg {
+        fn clone(&self) -> Msg {
+            if PANIC.swap(false, Ordering::SeqCst) {
+                panic!("cannot clone");
+            }
+            Msg(self.0)
+        }
+    }
+
+    let (s, mut r) = broadcast(1);
+    s.send(Msg(1)).unwrap();
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| r.try_recv())).is_err());
+
+    // The channel keeps working, and the message can still be received.
+    assert_eq!(r.try_recv(), Ok(Msg(1)));
+    assert_eq!(s.send(Msg(2)), Ok(1));
+    drop(r);
+    assert_eq!(s.receiver_count(), 0);
+}
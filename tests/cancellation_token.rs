@@ -0,0 +1,128 @@
+#![cfg(all(feature = "unstable", not(target_os = "unknown")))]
+
+use std::time::Duration;
+
+use async_std::future;
+use async_std::task::{self, CancellationToken};
+
+#[test]
+fn cancel_wakes_waiters() {
+    task::block_on(async {
+        let token = CancellationToken::new();
+        assert!(!token.is_cancelled());
+
+        let handles: Vec<_> = (0..3)
+            .map(|_| {
+                let token = token.clone();
+                task::spawn(async move { token.cancelled().await })
+            })
+            .collect();
+
+        task::sleep(Duration::from_millis(20)).await;
+        token.cancel();
+        assert!(token.is_cancelled());
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        // Completes immediately once cancelled.
+        token.cancelled().await;
+    })
+}
+
+#[test]
+fn child_tokens() {
+    task::block_on(async {
+        let parent = CancellationToken::new();
+        let child = parent.child_token();
+        let grandchild = child.child_token();
+        let sibling = parent.child_token();
+
+        child.cancel();
+        assert!(child.is_cancelled());
+        assert!(grandchild.is_cancelled());
+        assert!(!parent.is_cancelled());
+        assert!(!sibling.is_cancelled());
+
+        let waiter = sibling.clone();
+        let handle = task::spawn(async move { waiter.cancelled().await });
+        parent.cancel();
+        handle.await.unwrap();
+        assert!(sibling.is_cancelled());
+
+        // Children of a cancelled token start out cancelled.
+        assert!(parent.child_token().is_cancelled());
+
+        // Cancellation reaches grandchildren whose parent token was dropped.
+        let parent = CancellationToken::new();
+        let grandchild = parent.child_token().child_token();
+        parent.cancel();
+        assert!(grandchild.is_cancelled());
+    })
+}
+
+#[test]
+fn dropped_waiter() {
+    task::block_on(async {
+        let token = CancellationToken::new();
+
+        let res = future::timeout(Duration::from_millis(10), token.cancelled()).await;
+        assert!(res.is_err());
+
+        token.cancel();
+        token.cancelled().await;
+    })
+}
+
+#[test]
+fn attached_to_task() {
+    task::block_on(async {
+        assert!(CancellationToken::current().is_none());
+
+        let token = CancellationToken::new();
+        let handle = task::Builder::new()
+            .cancellation_token(token.child_token())
+            .spawn(async {
+                let token = CancellationToken::current().unwrap();
+                token.cancelled().await;
+                "stopped"
+            })
+            .unwrap();
+
+        token.cancel();
+        assert_eq!(handle.await.unwrap(), "stopped");
+    })
+}
+
+#[test]
+fn inherited_by_subtasks() {
+    task::block_on(async {
+        let token = CancellationToken::new();
+        let handle = task::Builder::new()
+            .cancellation_token(token.clone())
+            .spawn(async {
+                // Subtasks get a child token, which can be cancelled on its own.
+                let own = task::spawn(async {
+                    let token = CancellationToken::current().unwrap();
+                    token.cancel();
+                    token.is_cancelled()
+                });
+                assert!(own.await.unwrap());
+                assert!(!CancellationToken::current().unwrap().is_cancelled());
+
+                let nested = task::spawn(async {
+                    task::spawn_blocking(|| CancellationToken::current().unwrap())
+                        .await
+                        .unwrap()
+                });
+                nested.await.unwrap()
+            })
+            .unwrap();
+
+        let blocking = handle.await.unwrap();
+        assert!(!blocking.is_cancelled());
+        token.cancel();
+        assert!(blocking.is_cancelled());
+    })
+}
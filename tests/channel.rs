@@ -4,7 +4,8 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
-use async_std::sync::channel;
+use async_std::prelude::*;
+use async_std::sync::{channel, unbounded, TrySendError};
 use async_std::task;
 use rand::{Rng, SeedableRng};
 
@@ -216,7 +217,7 @@ fn len() {
             assert!(len <= CAP);
         }
 
-        child.await;
+        child.await.unwrap();
 
         assert_eq!(s.len(), 0);
         assert_eq!(r.len(), 0);
@@ -236,7 +237,7 @@ fn disconnect_wakes_receiver() {
         task::sleep(ms(1000)).await;
         drop(s);
 
-        child.await;
+        child.await.unwrap();
     })
 }
 
@@ -260,7 +261,7 @@ fn spsc() {
         }
         drop(s);
 
-        child.await;
+        child.await.unwrap();
     })
 }
 
@@ -298,7 +299,7 @@ fn mpmc() {
         }
 
         for t in tasks {
-            t.await;
+            t.await.unwrap();
         }
 
         for c in v.iter() {
@@ -319,8 +320,8 @@ fn oneshot() {
             let c1 = spawn(async move { r.recv().await.unwrap() });
             let c2 = spawn(async move { s.send(0).await });
 
-            c1.await;
-            c2.await;
+            c1.await.unwrap();
+            c2.await.unwrap();
         }
     })
 }
@@ -363,7 +364,7 @@ fn drops() {
                 s.send(DropCounter).await;
             }
 
-            child.await;
+            child.await.unwrap();
 
             for _ in 0..additional {
                 s.send(DropCounter).await;
@@ -376,3 +377,186 @@ fn drops() {
         })
     }
 }
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn unbounded_smoke() {
+    task::block_on(async {
+        let (s, r) = unbounded();
+        assert_eq!(s.capacity(), usize::MAX);
+        assert_eq!(r.capacity(), usize::MAX);
+
+        // Sending never waits, however many messages are in the channel.
+        for i in 0..10_000 {
+            s.send(i).await;
+            assert!(s.try_send(i).is_ok());
+        }
+        assert_eq!(r.len(), 20_000);
+        assert!(!r.is_full());
+
+        for i in 0..10_000 {
+            assert_eq!(r.recv().await.unwrap(), i);
+            assert_eq!(r.try_recv().unwrap(), i);
+        }
+        assert!(r.is_empty());
+
+        drop(s);
+        assert!(r.recv().await.is_err());
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn unbounded_recv_after_disconnect() {
+    task::block_on(async {
+        let (s, r) = unbounded();
+
+        s.send(1).await;
+        s.send(2).await;
+        drop(s);
+        assert!(r.is_disconnected());
+
+        assert_eq!(r.recv().await.unwrap(), 1);
+        assert_eq!(r.recv().await.unwrap(), 2);
+        assert!(r.recv().await.is_err());
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn unbounded_disconnect_wakes_receiver() {
+    task::block_on(async {
+        let (s, r) = unbounded::<()>();
+
+        let child = spawn(async move {
+            assert!(r.recv().await.is_err());
+        });
+
+        task::sleep(ms(100)).await;
+        drop(s);
+
+        child.await.unwrap();
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn unbounded_stream() {
+    task::block_on(async {
+        let (s, mut r) = unbounded();
+
+        let child = spawn(async move {
+            for i in 0..100 {
+                s.send(i).await;
+                if i % 10 == 0 {
+                    task::sleep(ms(1)).await;
+                }
+            }
+        });
+
+        let mut expected = 0;
+        while let Some(i) = r.next().await {
+            assert_eq!(i, expected);
+            expected += 1;
+        }
+        assert_eq!(expected, 100);
+
+        child.await.unwrap();
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn is_disconnected() {
+    task::block_on(async {
+        let (s, r) = channel::<()>(1);
+        assert!(!s.is_disconnected());
+        assert!(!r.is_disconnected());
+        drop(r);
+        assert!(s.is_disconnected());
+
+        let (s, r) = unbounded::<()>();
+        let s2 = s.clone();
+        drop(s);
+        assert!(!r.is_disconnected());
+        drop(s2);
+        assert!(r.is_disconnected());
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn unbounded_mpmc() {
+    const COUNT: usize = 25_000;
+    const TASKS: usize = 4;
+
+    task::block_on(async {
+        let (s, r) = unbounded::<usize>();
+        let v = Arc::new((0..COUNT).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>());
+        let mut tasks = Vec::new();
+
+        for _ in 0..TASKS {
+            let r = r.clone();
+            let v = v.clone();
+            tasks.push(spawn(async move {
+                for _ in 0..COUNT {
+                    let n = r.recv().await.unwrap();
+                    v[n].fetch_add(1, Ordering::SeqCst);
+                }
+            }));
+        }
+
+        for _ in 0..TASKS {
+            let s = s.clone();
+            tasks.push(spawn(async move {
+                for i in 0..COUNT {
+                    s.send(i).await;
+                }
+            }));
+        }
+
+        for t in tasks {
+            t.await.unwrap();
+        }
+
+        for c in v.iter() {
+            assert_eq!(c.load(Ordering::SeqCst), TASKS);
+        }
+    });
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn unbounded_drops() {
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug, PartialEq)]
+    struct DropCounter;
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    task::block_on(async {
+        let (s, r) = unbounded::<DropCounter>();
+        for _ in 0..10 {
+            s.send(DropCounter).await;
+        }
+        r.recv().await.unwrap();
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+
+        // Messages sent after all receivers are gone are given back.
+        drop(r);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+        match s.try_send(DropCounter) {
+            Err(TrySendError::Disconnected(msg)) => drop(msg),
+            res => panic!("unexpected result: {:?}", res),
+        }
+        assert_eq!(DROPS.load(Ordering::SeqCst), 2);
+
+        drop(s);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 11);
+    })
+}
@@ -0,0 +1,104 @@
+#![cfg(all(feature = "unstable", not(target_os = "unknown")))]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::sync::channel;
+use async_std::task;
+
+#[test]
+fn is_finished() {
+    task::block_on(async {
+        let (sender, receiver) = channel::<()>(1);
+        let handle = task::spawn(async move { receiver.recv().await.is_ok() });
+
+        // The task can't finish before it receives the message.
+        assert!(!handle.is_finished());
+
+        sender.send(()).await;
+        while !handle.is_finished() {
+            task::yield_now().await;
+        }
+        assert!(handle.await.unwrap());
+
+        let handle = task::spawn(async { panic!("boom") });
+        while !handle.is_finished() {
+            task::yield_now().await;
+        }
+        assert!(handle.join().await.unwrap_err().is_panic());
+    });
+}
+
+#[test]
+fn finished_result_is_ready() {
+    task::block_on(async {
+        // A finished task has stored its result, so joining it doesn't have to wait.
+        for i in 0..1000 {
+            let handle = task::spawn(async move { i });
+            while !handle.is_finished() {
+                task::yield_now().await;
+            }
+            let res = handle.join_timeout(Duration::from_secs(0)).await;
+            assert_eq!(res.ok().unwrap().unwrap(), i);
+
+            let handle = task::spawn_blocking(move || i);
+            while !handle.is_finished() {
+                task::yield_now().await;
+            }
+            let res = handle.join_timeout(Duration::from_secs(0)).await;
+            assert_eq!(res.ok().unwrap().unwrap(), i);
+        }
+
+        let handle = task::spawn(async { panic!("boom") });
+        while !handle.is_finished() {
+            task::yield_now().await;
+        }
+        let res = handle.join_timeout(Duration::from_secs(0)).await;
+        assert!(res.ok().unwrap().unwrap_err().is_panic());
+    });
+}
+
+#[test]
+fn join_timeout() {
+    task::block_on(async {
+        let (sender, receiver) = channel::<u32>(1);
+        let handle = task::spawn(async move { receiver.recv().await.unwrap() });
+
+        // The handle is given back on timeout and can be joined again.
+        let handle = handle
+            .join_timeout(Duration::from_millis(10))
+            .await
+            .unwrap_err();
+        assert!(!handle.is_finished());
+
+        sender.send(7).await;
+        let res = handle.join_timeout(Duration::from_secs(10)).await;
+        assert_eq!(res.ok().unwrap().unwrap(), 7);
+
+        let handle = task::spawn(async { panic!("boom") });
+        let res = handle.join_timeout(Duration::from_secs(10)).await;
+        assert!(res.ok().unwrap().unwrap_err().is_panic());
+    });
+}
+
+#[test]
+fn detach() {
+    task::block_on(async {
+        let done = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = channel::<()>(1);
+
+        let d = done.clone();
+        task::spawn(async move {
+            receiver.recv().await.unwrap();
+            d.store(true, Ordering::SeqCst);
+        })
+        .detach();
+
+        // The detached task keeps running.
+        sender.send(()).await;
+        while !done.load(Ordering::SeqCst) {
+            task::yield_now().await;
+        }
+    });
+}
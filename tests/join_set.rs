@@ -0,0 +1,73 @@
+#![cfg(all(feature = "unstable", not(target_os = "unknown")))]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::task::{self, JoinSet};
+
+#[test]
+fn completion_order() {
+    task::block_on(async {
+        let mut set = JoinSet::new();
+        for i in (0..3u64).rev() {
+            set.spawn(async move {
+                task::sleep(Duration::from_millis(i * 50)).await;
+                i
+            });
+        }
+        assert_eq!(set.len(), 3);
+
+        let mut order = Vec::new();
+        while let Some(res) = set.join_next().await {
+            order.push(res.unwrap());
+        }
+        assert_eq!(order, [0, 1, 2]);
+        assert!(set.is_empty());
+    })
+}
+
+#[test]
+fn stream() {
+    task::block_on(async {
+        let mut set = JoinSet::new();
+        set.push(task::spawn(async { 1 }));
+        set.spawn(async { panic!("boom") });
+
+        let results: Vec<_> = set.collect().await;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+        assert!(results.iter().any(|r| matches!(r, Err(e) if e.is_panic())));
+    })
+}
+
+#[test]
+fn drop_cancels_tasks() {
+    struct Guard(Arc<AtomicUsize>);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    task::block_on(async {
+        let dropped = Arc::new(AtomicUsize::new(0));
+
+        let mut set = JoinSet::new();
+        for _ in 0..3 {
+            let guard = Guard(dropped.clone());
+            set.spawn(async move {
+                let _guard = guard;
+                future::pending::<()>().await;
+            });
+        }
+        drop(set);
+
+        while dropped.load(Ordering::SeqCst) < 3 {
+            task::sleep(Duration::from_millis(10)).await;
+        }
+    })
+}
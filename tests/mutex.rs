@@ -73,6 +73,9 @@ fn contention() {
         }
 
         for handle in handles.into_iter() {
+            #[cfg(feature = "unstable")]
+            handle.await.unwrap();
+            #[cfg(not(feature = "unstable"))]
             handle.await;
         }
 
@@ -0,0 +1,83 @@
+#![cfg(feature = "unstable")]
+
+use async_std::sync::{oneshot, Canceled, TryRecvError};
+use async_std::task;
+
+#[cfg(not(target_os = "unknown"))]
+use async_std::task::spawn;
+#[cfg(target_os = "unknown")]
+use async_std::task::spawn_local as spawn;
+
+#[cfg(target_arch = "wasm32")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn smoke() {
+    task::block_on(async {
+        let (s, r) = oneshot();
+        s.send(7).unwrap();
+        assert_eq!(r.await, Ok(7));
+
+        // The receiver is woken up once the value is sent from another task.
+        let (s, r) = oneshot();
+        let mut r = Box::pin(r);
+        assert!(futures::poll!(r.as_mut()).is_pending());
+        spawn(async move { s.send("hello".to_string()).unwrap() });
+        assert_eq!(r.await.unwrap(), "hello");
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn sender_dropped() {
+    task::block_on(async {
+        let (s, r) = oneshot::<i32>();
+        let mut r = Box::pin(r);
+        assert!(futures::poll!(r.as_mut()).is_pending());
+        spawn(async move { drop(s) });
+        assert_eq!(r.await, Err(Canceled));
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn receiver_dropped() {
+    task::block_on(async {
+        let (s, r) = oneshot::<i32>();
+        let mut closed = Box::pin(s.closed());
+        assert!(futures::poll!(closed.as_mut()).is_pending());
+        assert!(!s.is_closed());
+
+        spawn(async move { drop(r) });
+        closed.await;
+        assert!(s.is_closed());
+        assert_eq!(s.send(1), Err(1));
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn try_recv() {
+    let (s, mut r) = oneshot();
+    assert_eq!(r.try_recv(), Err(TryRecvError::Empty));
+    s.send(1).unwrap();
+    assert_eq!(r.try_recv(), Ok(1));
+    assert_eq!(r.try_recv(), Err(TryRecvError::Disconnected));
+
+    let (s, mut r) = oneshot::<i32>();
+    drop(s);
+    assert_eq!(r.try_recv(), Err(TryRecvError::Disconnected));
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn drops_unreceived_value() {
+    use std::sync::Arc;
+
+    let value = Arc::new(());
+    let (s, r) = oneshot();
+    s.send(value.clone()).unwrap();
+    drop(r);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
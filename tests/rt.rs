@@ -0,0 +1,296 @@
+#![cfg(all(feature = "unstable", not(target_os = "unknown")))]
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use async_std::rt;
+use async_std::task;
+
+#[test]
+fn isolated_runtime() {
+    let runtime = rt::Builder::new()
+        .thread_count(1)
+        .thread_name("isolated".to_string())
+        .build()
+        .unwrap();
+
+    let name = runtime.block_on(async {
+        task::spawn(async {
+            // Tasks spawned from within the runtime stay on it.
+            task::spawn(async { thread::current().name().map(String::from) })
+                .await
+                .unwrap()
+        })
+        .await
+        .unwrap()
+    });
+    assert_eq!(name.as_deref(), Some("isolated"));
+
+    let handle = runtime.spawn(async {
+        task::sleep(Duration::from_millis(10)).await;
+        1 + 2
+    });
+    assert_eq!(task::block_on(handle).unwrap(), 3);
+    assert_eq!(runtime.shutdown(Duration::from_secs(1)), 0);
+}
+
+#[test]
+fn blocking_stays_on_runtime() {
+    let runtime = rt::Builder::new()
+        .thread_count(1)
+        .thread_name("isolated".to_string())
+        .build()
+        .unwrap();
+
+    let handle = runtime.enter(|| {
+        task::spawn_blocking(|| {
+            // Tasks spawned from a blocking task run on the runtime that spawned it.
+            task::block_on(task::spawn(async {
+                thread::current().name().map(String::from)
+            }))
+            .unwrap()
+        })
+    });
+    assert_eq!(task::block_on(handle).unwrap().as_deref(), Some("isolated"));
+}
+
+#[test]
+fn drop_in_own_task() {
+    use std::sync::Mutex;
+
+    let runtime = rt::Builder::new().thread_count(1).build().unwrap();
+    let slot = Arc::new(Mutex::new(Some(runtime)));
+
+    let handle = {
+        let runtime = slot.lock().unwrap();
+        let slot = slot.clone();
+        runtime.as_ref().unwrap().spawn(async move {
+            // The runtime can't join the thread running this task.
+            drop(slot.lock().unwrap().take());
+            1 + 2
+        })
+    };
+
+    assert_eq!(task::block_on(handle).unwrap(), 3);
+    assert!(slot.lock().unwrap().is_none());
+}
+
+#[test]
+fn runtime_metrics() {
+    let runtime = rt::Builder::new().thread_count(2).build().unwrap();
+
+    let metrics = runtime.metrics();
+    assert_eq!(metrics.spawned_tasks(), 0);
+    assert_eq!(metrics.worker_count(), 2);
+
+    runtime.spawn(async_std::future::pending::<()>());
+    runtime.block_on(async {
+        for _ in 0..3 {
+            task::spawn(async { thread::sleep(Duration::from_millis(10)) })
+                .await
+                .unwrap();
+        }
+    });
+
+    let metrics = runtime.metrics();
+    assert_eq!(metrics.spawned_tasks(), 4);
+    assert_eq!(metrics.completed_tasks(), 3);
+    assert_eq!(metrics.live_tasks(), 1);
+    assert_eq!(metrics.queue_depth(), 0);
+
+    // Busy time is recorded right after a task runs, which may be after it has been awaited.
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        let busy: Duration = runtime.metrics().worker_busy_time().iter().sum();
+        if busy >= Duration::from_millis(30) {
+            break;
+        }
+        assert!(Instant::now() < deadline, "busy time is not recorded");
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    assert_eq!(runtime.shutdown(Duration::from_millis(10)), 1);
+}
+
+#[test]
+fn shutdown_blocked_poll() {
+    use std::sync::mpsc;
+
+    let runtime = rt::Builder::new().thread_count(1).build().unwrap();
+
+    // A task that blocks inside a poll until it is released.
+    let (started, ready) = mpsc::channel();
+    let (release, blocked) = mpsc::channel::<()>();
+    runtime.spawn(async move {
+        started.send(()).unwrap();
+        blocked.recv().unwrap();
+    });
+    ready.recv().unwrap();
+
+    let start = Instant::now();
+    assert_eq!(runtime.shutdown(Duration::from_millis(50)), 1);
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    release.send(()).unwrap();
+}
+
+#[test]
+fn blocking_pool() {
+    let runtime = rt::Builder::new()
+        .thread_count(1)
+        .max_blocking_threads(2)
+        .blocking_keep_alive(Duration::from_millis(10))
+        .blocking_thread_name("blocking".to_string())
+        .build()
+        .unwrap();
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            runtime.enter(|| {
+                task::spawn_blocking(|| {
+                    thread::sleep(Duration::from_millis(20));
+                    thread::current().name().map(String::from)
+                })
+            })
+        })
+        .collect();
+
+    let metrics = runtime.metrics();
+    assert_eq!(metrics.blocking_tasks(), 4);
+    assert_eq!(metrics.blocking_threads(), 2);
+
+    for handle in handles {
+        let name = task::block_on(handle).unwrap();
+        assert_eq!(name.as_deref(), Some("blocking"));
+    }
+
+    // Idle threads exit once the keep-alive timeout expires.
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while runtime.metrics().blocking_threads() > 0 {
+        assert!(Instant::now() < deadline, "idle blocking threads don't exit");
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[test]
+fn blocking_pool_shutdown() {
+    use std::cell::RefCell;
+    use std::sync::mpsc;
+
+    // Signals that a thread has exited when dropped with its thread-locals.
+    struct Exit(mpsc::Sender<()>);
+
+    impl Drop for Exit {
+        fn drop(&mut self) {
+            let _ = self.0.send(());
+        }
+    }
+
+    thread_local! {
+        static EXIT: RefCell<Option<Exit>> = RefCell::new(None);
+    }
+
+    let runtime = rt::Builder::new()
+        .thread_count(1)
+        .blocking_keep_alive(Duration::from_secs(3600))
+        .build()
+        .unwrap();
+
+    let (sender, receiver) = mpsc::channel();
+    let handle = runtime.enter(|| {
+        task::spawn_blocking(move || EXIT.with(|exit| *exit.borrow_mut() = Some(Exit(sender))))
+    });
+    task::block_on(handle).unwrap();
+
+    // The idle blocking thread exits without waiting for the keep-alive timeout.
+    runtime.shutdown(Duration::from_secs(0));
+    receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+}
+
+#[test]
+fn panic_policy() {
+    let panicked = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let log = panicked.clone();
+
+    let runtime = rt::Builder::new()
+        .thread_count(1)
+        .panic_policy(rt::PanicPolicy::callback(move |task, payload| {
+            let name = task.name().unwrap_or("").to_string();
+            let message = payload.downcast_ref::<&str>().unwrap().to_string();
+            log.lock().unwrap().push((name, message));
+        }))
+        .build()
+        .unwrap();
+
+    let err = runtime.block_on(async {
+        let handle = task::Builder::new()
+            .name("doomed".to_string())
+            .spawn(async { panic!("boom") })
+            .unwrap();
+        handle.await.unwrap_err()
+    });
+    assert!(err.is_panic());
+    assert_eq!(err.to_string(), "task panicked: boom");
+
+    // The error is thread-safe, so it can be boxed like any other error.
+    let err: Box<dyn std::error::Error + Send + Sync> = Box::new(err);
+    assert_eq!(err.to_string(), "task panicked: boom");
+    assert_eq!(
+        *panicked.lock().unwrap(),
+        vec![("doomed".to_string(), "boom".to_string())]
+    );
+
+    // The runtime thread survived the panic.
+    assert_eq!(runtime.block_on(runtime.spawn(async { 1 + 2 })).unwrap(), 3);
+
+    // Awaiting a task cancelled by a shutdown doesn't panic either.
+    let handle = runtime.spawn(async_std::future::pending::<()>());
+    runtime.shutdown(Duration::from_secs(0));
+    let err = task::block_on(handle).unwrap_err();
+    assert!(err.is_cancelled());
+}
+
+#[test]
+fn priorities() {
+    use std::sync::{mpsc, Mutex};
+
+    use async_std::task::Priority;
+
+    let runtime = rt::Builder::new().thread_count(1).build().unwrap();
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    // Keep the only worker busy while tasks of every priority get scheduled.
+    let (started, ready) = mpsc::channel();
+    let (release, blocked) = mpsc::channel::<()>();
+    let blocker = runtime.spawn(async move {
+        started.send(()).unwrap();
+        blocked.recv().unwrap();
+    });
+    ready.recv().unwrap();
+
+    let handles: Vec<_> = [Priority::Low, Priority::Normal, Priority::High]
+        .iter()
+        .map(|&priority| {
+            let order = order.clone();
+            runtime.enter(|| {
+                task::Builder::new()
+                    .priority(priority)
+                    .spawn(async move { order.lock().unwrap().push(priority) })
+                    .unwrap()
+            })
+        })
+        .collect();
+    release.send(()).unwrap();
+
+    task::block_on(async {
+        blocker.await.unwrap();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+    });
+    assert_eq!(
+        *order.lock().unwrap(),
+        [Priority::High, Priority::Normal, Priority::Low]
+    );
+}
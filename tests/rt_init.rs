@@ -0,0 +1,35 @@
+#![cfg(all(feature = "unstable", not(target_os = "unknown")))]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::rt;
+use async_std::task;
+
+#[test]
+fn builder_init() {
+    let started = Arc::new(AtomicUsize::new(0));
+    let counter = started.clone();
+
+    rt::Builder::new()
+        .thread_count(2)
+        .thread_name("rt-test".to_string())
+        .on_thread_start(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .init()
+        .unwrap();
+
+    task::block_on(async {
+        // Wait until both runtime threads have invoked the start hook.
+        while started.load(Ordering::SeqCst) < 2 {
+            task::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(task::spawn(async { 1 + 2 }).await.unwrap(), 3);
+    });
+
+    // The runtime has already been started, so it can't be configured anymore.
+    let err = rt::Builder::new().thread_count(1).init().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+}
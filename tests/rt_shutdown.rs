@@ -0,0 +1,46 @@
+#![cfg(all(feature = "unstable", not(target_os = "unknown")))]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use async_std::future;
+use async_std::rt;
+use async_std::task;
+
+#[test]
+fn shutdown() {
+    static FINISHED: AtomicBool = AtomicBool::new(false);
+    static DROPPED: AtomicBool = AtomicBool::new(false);
+
+    struct DropFlag;
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            DROPPED.store(true, Ordering::SeqCst);
+        }
+    }
+
+    // A short task that completes within the timeout.
+    task::spawn(async {
+        task::sleep(Duration::from_millis(50)).await;
+        FINISHED.store(true, Ordering::SeqCst);
+    });
+
+    // A task that never completes on its own.
+    let stuck = task::spawn(async {
+        let _flag = DropFlag;
+        future::pending::<()>().await;
+    });
+
+    let cancelled = rt::shutdown(Duration::from_millis(500));
+    assert_eq!(cancelled, 1);
+    assert!(FINISHED.load(Ordering::SeqCst));
+    assert!(DROPPED.load(Ordering::SeqCst));
+    drop(stuck);
+
+    // The runtime no longer accepts new tasks.
+    let res = task::Builder::new().spawn(async {});
+    assert!(res.is_err());
+
+    assert_eq!(rt::shutdown(Duration::from_millis(0)), 0);
+}
@@ -188,6 +188,9 @@ fn writer_and_readers() {
     task::block_on(async move {
         // Wait for readers to pass their asserts.
         for r in readers {
+            #[cfg(feature = "unstable")]
+            r.await.unwrap();
+            #[cfg(not(feature = "unstable"))]
             r.await;
         }
 
@@ -0,0 +1,58 @@
+#![cfg(all(feature = "unstable", not(target_os = "unknown")))]
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use async_std::rt::TestRuntime;
+use async_std::task;
+
+fn run(runtime: &TestRuntime) -> Vec<usize> {
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    runtime.block_on(async {
+        let handles: Vec<_> = (0..5)
+            .map(|i| {
+                let order = order.clone();
+                task::spawn(async move {
+                    task::yield_now().await;
+                    order.lock().unwrap().push(i);
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+    });
+
+    let order = order.lock().unwrap().clone();
+    order
+}
+
+#[test]
+fn fifo_without_seed() {
+    assert_eq!(run(&TestRuntime::new()), vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn replay_seed() {
+    for seed in 0..20 {
+        let runtime = TestRuntime::with_seed(seed);
+        assert_eq!(runtime.seed(), Some(seed));
+        assert_eq!(run(&runtime), run(&TestRuntime::with_seed(seed)));
+    }
+}
+
+#[test]
+fn seeds_explore_orders() {
+    let orders: HashSet<Vec<usize>> = (0..20)
+        .map(|seed| run(&TestRuntime::with_seed(seed)))
+        .collect();
+    assert!(orders.len() > 1);
+}
+
+#[test]
+#[should_panic(expected = "the test runtime panicked with seed 7: boom")]
+fn panic_reports_seed() {
+    TestRuntime::with_seed(7).block_on(async { panic!("boom") });
+}
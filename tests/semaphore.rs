@@ -0,0 +1,191 @@
+#![cfg(feature = "unstable")]
+
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use async_std::future;
+use async_std::sync::{channel, Semaphore, TryAcquireError};
+use async_std::task;
+use futures::task::{waker, ArcWake};
+
+#[cfg(not(target_os = "unknown"))]
+use async_std::task::spawn;
+#[cfg(target_os = "unknown")]
+use async_std::task::spawn_local as spawn;
+
+#[cfg(target_arch = "wasm32")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn smoke() {
+    task::block_on(async {
+        let s = Semaphore::new(2);
+
+        let p1 = s.acquire().await.unwrap();
+        let p2 = s.try_acquire().unwrap();
+        assert_eq!(s.available_permits(), 0);
+        assert_eq!(s.try_acquire().unwrap_err(), TryAcquireError::NoPermits);
+
+        drop(p1);
+        assert_eq!(s.available_permits(), 1);
+        p2.forget();
+        assert_eq!(s.available_permits(), 1);
+
+        s.add_permits(2);
+        let p = s.acquire_many(3).await.unwrap();
+        assert_eq!(p.permits(), 3);
+        assert!(s.try_acquire_many(1).is_err());
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn concurrency_limit() {
+    task::block_on(async {
+        let s = Arc::new(Semaphore::new(3));
+        let active = Arc::new(AtomicUsize::new(0));
+        let mut handles = Vec::new();
+
+        for _ in 0..20 {
+            let permit = s.clone().acquire_owned().await.unwrap();
+            let active = active.clone();
+            handles.push(spawn(async move {
+                assert!(active.fetch_add(1, Ordering::SeqCst) < 3);
+                task::sleep(ms(1)).await;
+                active.fetch_sub(1, Ordering::SeqCst);
+                drop(permit);
+            }));
+        }
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        assert_eq!(s.available_permits(), 3);
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn acquire_many_waits() {
+    task::block_on(async {
+        let s = Arc::new(Semaphore::new(1));
+        let (waiting, is_waiting) = channel(1);
+
+        let s2 = s.clone();
+        let handle = spawn(async move {
+            let mut acquire = Box::pin(s2.acquire_many_owned(3));
+            assert!(futures::poll!(acquire.as_mut()).is_pending());
+            waiting.send(()).await;
+            acquire.await.unwrap().permits()
+        });
+
+        // A waiter for a single permit must not be starved by the one waiting for three.
+        is_waiting.recv().await.unwrap();
+        let single = s.acquire().await.unwrap();
+        drop(single);
+
+        s.add_permits(2);
+        assert_eq!(handle.await.unwrap(), 3);
+        assert_eq!(s.available_permits(), 3);
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn wakes_satisfiable_waiters() {
+    struct Wakes(AtomicUsize);
+
+    impl ArcWake for Wakes {
+        fn wake_by_ref(wakes: &Arc<Wakes>) {
+            wakes.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let s = Semaphore::new(0);
+    let wakes = Arc::new(Wakes(AtomicUsize::new(0)));
+    let waker = waker(wakes.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut many = Box::pin(s.acquire_many(2));
+    let mut one = Box::pin(s.acquire());
+    let mut other = Box::pin(s.acquire());
+    assert!(many.as_mut().poll(&mut cx).is_pending());
+    assert!(one.as_mut().poll(&mut cx).is_pending());
+    assert!(other.as_mut().poll(&mut cx).is_pending());
+
+    // A single permit wakes up a single waiter that needs one.
+    s.add_permits(1);
+    assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+    match one.as_mut().poll(&mut cx) {
+        Poll::Ready(Ok(permit)) => permit.forget(),
+        _ => panic!("the permit was not acquired"),
+    }
+
+    // Two permits wake up the waiter that needs both of them.
+    s.add_permits(2);
+    assert_eq!(wakes.0.load(Ordering::SeqCst), 2);
+
+    // A woken waiter that is cancelled passes its permits on.
+    drop(many);
+    assert_eq!(wakes.0.load(Ordering::SeqCst), 3);
+    assert!(matches!(other.as_mut().poll(&mut cx), Poll::Ready(Ok(_))));
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn cancelled_acquire() {
+    task::block_on(async {
+        let s = Semaphore::new(0);
+
+        assert!(future::timeout(ms(10), s.acquire()).await.is_err());
+        s.add_permits(1);
+        assert!(s.acquire().await.is_ok());
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn close() {
+    task::block_on(async {
+        let s = Arc::new(Semaphore::new(1));
+        let permit = s.try_acquire().unwrap();
+
+        let (waiting, is_waiting) = channel(1);
+
+        let s2 = s.clone();
+        let handle = spawn(async move {
+            let mut acquire = Box::pin(s2.acquire());
+            assert!(futures::poll!(acquire.as_mut()).is_pending());
+            waiting.send(()).await;
+            acquire.await.is_err()
+        });
+
+        // Closing the semaphore wakes up the waiter.
+        is_waiting.recv().await.unwrap();
+        s.close();
+        assert!(handle.await.unwrap());
+        assert_eq!(s.try_acquire().unwrap_err(), TryAcquireError::Closed);
+
+        // Permits that are held stay valid and are still given back.
+        drop(permit);
+        assert_eq!(s.available_permits(), 1);
+    })
+}
+
+#[test]
+#[should_panic(expected = "cannot add")]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn add_permits_overflow() {
+    let s = Semaphore::new(usize::MAX - 1);
+    s.add_permits(1);
+    assert_eq!(s.available_permits(), usize::MAX);
+    s.add_permits(1);
+}
@@ -0,0 +1,82 @@
+#![cfg(all(feature = "unstable", not(target_os = "unknown")))]
+
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+
+use async_std::rt;
+use async_std::task;
+
+#[test]
+fn stays_on_worker() {
+    let runtime = rt::Builder::new().thread_count(3).build().unwrap();
+
+    for worker in 0..3 {
+        let handle = runtime.enter(|| {
+            task::Builder::new()
+                .spawn_on(worker, || async {
+                    // `Rc` is not `Send`, so this future couldn't be spawned with `task::spawn`.
+                    let thread = Rc::new(thread::current().id());
+                    for _ in 0..10 {
+                        task::sleep(Duration::from_millis(1)).await;
+                        assert_eq!(*thread, thread::current().id());
+                    }
+                    format!("{:?}", thread)
+                })
+                .unwrap()
+        });
+        let first = task::block_on(handle).unwrap();
+
+        let handle = runtime.enter(|| {
+            task::Builder::new()
+                .spawn_on(worker, || async { format!("{:?}", thread::current().id()) })
+                .unwrap()
+        });
+        assert_eq!(task::block_on(handle).unwrap(), first);
+    }
+}
+
+#[test]
+fn invalid_worker() {
+    let runtime = rt::Builder::new().thread_count(2).build().unwrap();
+
+    let err = runtime
+        .enter(|| task::Builder::new().spawn_on(2, || async {}))
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn round_robin() {
+    let runtime = rt::Builder::new().thread_count(2).build().unwrap();
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let handle = runtime.enter(|| task::spawn_pinned(|| async { thread::current().id() }));
+            task::block_on(handle).unwrap()
+        })
+        .collect();
+
+    assert_ne!(threads[0], threads[1]);
+    assert_eq!(threads[0], threads[2]);
+    assert_eq!(threads[1], threads[3]);
+}
+
+#[test]
+fn task_locals() {
+    task::block_on(async {
+        let parent = task::current().id();
+        let (id, current) = task::Builder::new()
+            .name("pinned".to_string())
+            .spawn_pinned(|| async {
+                let task = task::current();
+                (task.id(), task.name().map(String::from))
+            })
+            .unwrap()
+            .await
+            .unwrap();
+
+        assert_ne!(id, parent);
+        assert_eq!(current.as_deref(), Some("pinned"));
+    })
+}
@@ -0,0 +1,171 @@
+#![cfg(all(feature = "unstable", not(target_os = "unknown")))]
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::future;
+use async_std::rt::TestRuntime;
+use async_std::task::{self, Supervisor, SupervisorError};
+
+#[test]
+fn restarts_failed_runs() {
+    task::block_on(async {
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let r = runs.clone();
+        let handle = Supervisor::new().name("worker".to_string()).spawn(move || {
+            let r = r.clone();
+            async move {
+                assert_eq!(task::current().name(), Some("worker"));
+                match r.fetch_add(1, Ordering::SeqCst) {
+                    0 => Err("error"),
+                    1 => panic!("boom"),
+                    n => Ok(n),
+                }
+            }
+        });
+
+        assert_eq!(handle.await.unwrap().unwrap(), 2);
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    });
+}
+
+#[test]
+fn gives_up() {
+    task::block_on(async {
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let r = runs.clone();
+        let res = Supervisor::new()
+            .max_restarts(2, Duration::from_secs(60))
+            .spawn(move || {
+                r.fetch_add(1, Ordering::SeqCst);
+                async { Err::<(), _>("error") }
+            })
+            .await
+            .unwrap();
+        match res {
+            Err(SupervisorError::Error(err)) => assert_eq!(err, "error"),
+            res => panic!("unexpected result: {:?}", res),
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+
+        let res = Supervisor::new()
+            .max_restarts(0, Duration::from_secs(60))
+            .spawn(|| async { panic!("boom") })
+            .await
+            .unwrap();
+        match res {
+            Err(SupervisorError::<()>::Join(err)) => assert!(err.is_panic()),
+            res => panic!("unexpected result: {:?}", res.map(|_: ()| ())),
+        }
+    });
+}
+
+#[test]
+fn backoff() {
+    let runtime = TestRuntime::new();
+    let start = runtime.now();
+
+    let res = runtime.block_on(async {
+        Supervisor::new()
+            .backoff(Duration::from_secs(1), Duration::from_secs(3))
+            .max_restarts(4, Duration::from_secs(3600))
+            .spawn(|| async { Err::<(), _>(()) })
+            .await
+            .unwrap()
+    });
+
+    // The restarts are delayed by 1, 2, 3 and 3 seconds.
+    assert!(res.is_err());
+    assert_eq!(runtime.now() - start, Duration::from_secs(9));
+}
+
+#[test]
+fn restart_window() {
+    let runtime = TestRuntime::new();
+    let runs = Arc::new(AtomicUsize::new(0));
+
+    let r = runs.clone();
+    let res = runtime.block_on(async {
+        Supervisor::new()
+            .max_restarts(1, Duration::from_secs(10))
+            .spawn(move || {
+                let r = r.clone();
+                async move {
+                    // The first runs fail far enough apart to stay within the limit.
+                    let n = r.fetch_add(1, Ordering::SeqCst);
+                    let delay = if n < 3 { 20 } else { 1 };
+                    task::sleep(Duration::from_secs(delay)).await;
+                    Err::<(), _>(n)
+                }
+            })
+            .await
+            .unwrap()
+    });
+
+    match res {
+        Err(SupervisorError::Error(n)) => assert_eq!(n, 3),
+        res => panic!("unexpected result: {:?}", res),
+    }
+    assert_eq!(runs.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+fn restart_after_shutdown() {
+    use async_std::rt;
+
+    let runtime = rt::Builder::new().thread_count(1).build().unwrap();
+    let handle = runtime.spawn(async {
+        Supervisor::new()
+            .backoff(Duration::from_millis(50), Duration::from_millis(50))
+            .spawn(|| async { Err::<(), _>("error") })
+            .await
+            .unwrap()
+    });
+
+    // The runtime stops accepting tasks while the supervisor waits to restart the failed run.
+    task::block_on(task::sleep(Duration::from_millis(10)));
+    assert_eq!(runtime.shutdown(Duration::from_secs(10)), 0);
+
+    match task::block_on(handle).unwrap() {
+        Err(SupervisorError::Spawn(_)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn cancel() {
+    struct Guard(Arc<AtomicBool>);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    task::block_on(async {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let started = Arc::new(AtomicBool::new(false));
+
+        let (d, s) = (dropped.clone(), started.clone());
+        let handle = Supervisor::new().spawn(move || {
+            let guard = Guard(d.clone());
+            s.store(true, Ordering::SeqCst);
+            async move {
+                let _guard = guard;
+                future::pending::<Result<(), ()>>().await
+            }
+        });
+
+        while !started.load(Ordering::SeqCst) {
+            task::yield_now().await;
+        }
+        assert!(handle.cancel().await.is_none());
+
+        while !dropped.load(Ordering::SeqCst) {
+            task::yield_now().await;
+        }
+    });
+}
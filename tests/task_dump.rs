@@ -0,0 +1,32 @@
+#![cfg(all(feature = "unstable", not(target_os = "unknown")))]
+
+use async_std::sync::channel;
+use async_std::task;
+
+#[test]
+fn dump() {
+    task::block_on(async {
+        let (sender, receiver) = channel::<()>(1);
+
+        let handle = task::Builder::new()
+            .name("waiter".to_string())
+            .spawn(async move { receiver.recv().await })
+            .unwrap();
+        let id = handle.task().id();
+
+        // Let the task run until it waits on the channel.
+        while !task::dump().iter().any(|t| t.id() == id && t.poll_count() > 0) {
+            task::yield_now().await;
+        }
+
+        let info = task::dump().into_iter().find(|t| t.id() == id).unwrap();
+        assert_eq!(info.name(), Some("waiter"));
+        assert_eq!(info.parent_id(), Some(task::current().id()));
+        assert!(info.location().file().ends_with("task_dump.rs"));
+        assert!(info.to_string().starts_with(&format!("task {} \"waiter\"", id)));
+
+        sender.send(()).await;
+        handle.await.unwrap().unwrap();
+        assert!(task::dump().iter().all(|t| t.id() != id));
+    });
+}
@@ -36,8 +36,155 @@ fn drop_local() {
 
     // Wait for the task to finish and make sure its task-local has been dropped.
     task::block_on(async {
+        #[cfg(feature = "unstable")]
+        handle.await.unwrap();
+        #[cfg(not(feature = "unstable"))]
         handle.await;
         assert!(DROP_LOCAL.load(Ordering::SeqCst));
         drop(task);
     });
 }
+
+#[cfg(feature = "unstable")]
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn inherit_local() {
+    use std::cell::Cell;
+
+    task_local! {
+        #[inherit]
+        static INHERITED: Cell<u32> = Cell::new(0);
+        static NOT_INHERITED: Cell<u32> = Cell::new(0);
+    }
+
+    task::block_on(async {
+        INHERITED.with(|v| v.set(1));
+        NOT_INHERITED.with(|v| v.set(1));
+
+        let handle = spawn(async {
+            let values = (INHERITED.with(|v| v.get()), NOT_INHERITED.with(|v| v.get()));
+
+            // The child has its own copy of the value.
+            INHERITED.with(|v| v.set(2));
+
+            // Grandchildren inherit the child's value.
+            let grandchild = spawn(async { INHERITED.with(|v| v.get()) }).await.unwrap();
+            (values, grandchild)
+        });
+
+        assert_eq!(handle.await.unwrap(), ((1, 0), 2));
+        assert_eq!(INHERITED.with(|v| v.get()), 1);
+    });
+}
+
+#[cfg(all(feature = "unstable", not(target_os = "unknown")))]
+#[test]
+fn inherit_local_blocking() {
+    use std::cell::Cell;
+
+    task_local! {
+        #[inherit]
+        static REQUEST_ID: Cell<u64> = Cell::new(0);
+    }
+
+    task::block_on(async {
+        REQUEST_ID.with(|id| id.set(7));
+
+        let id = task::spawn_blocking(|| REQUEST_ID.with(|id| id.get()))
+            .await
+            .unwrap();
+        assert_eq!(id, 7);
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn set_and_replace() {
+    task_local! {
+        static NAME: String = "initial".to_string();
+    }
+
+    assert!(std::panic::catch_unwind(|| NAME.set(String::new())).is_err());
+
+    task::block_on(async {
+        assert_eq!(NAME.replace("first".to_string()), "initial");
+        NAME.set("second".to_string());
+        assert_eq!(NAME.with(|n| n.clone()), "second");
+
+        // Other tasks have their own values.
+        let other = spawn(async { NAME.with(|n| n.clone()) }).await.unwrap();
+        assert_eq!(other, "initial");
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn replace_while_borrowed() {
+    task_local! {
+        static VALUE: u32 = 0;
+    }
+
+    task::block_on(async {
+        let res = std::panic::catch_unwind(|| VALUE.with(|_| VALUE.set(1)));
+        assert!(res.is_err());
+
+        // The borrow is released after the panic.
+        VALUE.set(2);
+        assert_eq!(VALUE.with(|v| *v), 2);
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn replace_while_inherited() {
+    struct Value(u32);
+
+    impl Clone for Value {
+        fn clone(&self) -> Value {
+            // Replacing the value would drop it while it is being cloned.
+            let res = std::panic::catch_unwind(|| VALUE.set(Value(0)));
+            assert!(res.is_err());
+            Value(self.0)
+        }
+    }
+
+    task_local! {
+        #[inherit]
+        static VALUE: Value = Value(0);
+    }
+
+    task::block_on(async {
+        VALUE.set(Value(1));
+
+        let inherited = spawn(async { VALUE.with(|v| v.0) }).await.unwrap();
+        assert_eq!(inherited, 1);
+        assert_eq!(VALUE.with(|v| v.0), 1);
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn scope() {
+    task_local! {
+        static DEPTH: u32 = 0;
+    }
+
+    task::block_on(async {
+        DEPTH.set(1);
+
+        let inner = DEPTH.scope(10, async {
+            assert_eq!(DEPTH.with(|d| *d), 10);
+            task::yield_now().await;
+            assert_eq!(DEPTH.replace(11), 10);
+            task::yield_now().await;
+            DEPTH.scope(20, async { DEPTH.with(|d| *d) }).await + DEPTH.with(|d| *d)
+        });
+
+        assert_eq!(inner.await, 31);
+        assert_eq!(DEPTH.with(|d| *d), 1);
+    });
+}
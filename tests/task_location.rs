@@ -0,0 +1,52 @@
+#![cfg(all(feature = "unstable", not(target_os = "unknown")))]
+
+use async_std::task::{self, JoinHandle};
+
+#[test]
+fn spawn_location() {
+    task::block_on(async {
+        let line = line!() + 1;
+        let handle = task::spawn(async { task::current().location() });
+        assert_eq!(handle.task().location().file(), file!());
+        assert_eq!(handle.task().location().line(), line);
+        assert_eq!(handle.await.unwrap().line(), line);
+    });
+}
+
+#[test]
+fn builder_location() {
+    let line = line!() + 1;
+    let location = task::Builder::new().blocking(async { task::current().location() });
+    assert_eq!((location.file(), location.line()), (file!(), line));
+
+    task::block_on(async {
+        let line = line!() + 1;
+        let handle = task::Builder::new().spawn(async {}).unwrap();
+        assert_eq!(handle.task().location().line(), line);
+        handle.await.unwrap();
+    });
+}
+
+#[test]
+fn track_caller() {
+    #[track_caller]
+    fn spawn_helper() -> JoinHandle<()> {
+        task::spawn(async {})
+    }
+
+    task::block_on(async {
+        let line = line!() + 1;
+        let handle = spawn_helper();
+        assert_eq!(handle.task().location().line(), line);
+        handle.await.unwrap();
+    });
+}
+
+#[test]
+fn spawn_blocking_location() {
+    task::block_on(async {
+        let line = line!() + 1;
+        let handle = task::spawn_blocking(|| task::current().location());
+        assert_eq!(handle.await.unwrap().line(), line);
+    });
+}
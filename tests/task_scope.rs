@@ -0,0 +1,106 @@
+#![cfg(all(feature = "unstable", not(target_os = "unknown")))]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use async_std::future;
+use async_std::task;
+
+#[test]
+fn borrow_from_stack() {
+    task::block_on(async {
+        let counter = AtomicUsize::new(0);
+        let counter = &counter;
+
+        let out = task::scope(|s| async move {
+            for _ in 0..10 {
+                s.spawn(async move {
+                    task::yield_now().await;
+                    counter.fetch_add(1, Ordering::SeqCst);
+                });
+            }
+            "done"
+        })
+        .await;
+
+        // The scope waits for all children, even if their handles were dropped.
+        assert_eq!(out, "done");
+        assert_eq!(counter.load(Ordering::SeqCst), 10);
+    })
+}
+
+#[test]
+fn nested_spawn() {
+    task::block_on(async {
+        let counter = AtomicUsize::new(0);
+        let counter = &counter;
+
+        task::scope(|s| async move {
+            let inner = s.clone();
+            let handle = s.spawn(async move {
+                let child = inner.spawn(async move { counter.fetch_add(1, Ordering::SeqCst) });
+                child.await + 10
+            });
+            assert_eq!(handle.await, 10);
+        })
+        .await;
+
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    })
+}
+
+#[test]
+fn children_are_tasks() {
+    task::block_on(async {
+        let parent = task::current().id();
+
+        task::scope(|s| async move {
+            let handle = s.spawn(async { task::current().id() });
+            let id = handle.task().id();
+            assert_ne!(id, parent);
+            assert_eq!(handle.await, id);
+        })
+        .await;
+    })
+}
+
+#[test]
+fn drop_cancels_children() {
+    struct Guard<'a>(&'a AtomicUsize);
+
+    impl Drop for Guard<'_> {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    task::block_on(async {
+        let dropped = AtomicUsize::new(0);
+        let dropped = &dropped;
+
+        let res = future::timeout(
+            Duration::from_millis(50),
+            task::scope(|s| async move {
+                s.spawn(async move {
+                    let _guard = Guard(dropped);
+                    future::pending::<()>().await;
+                });
+            }),
+        )
+        .await;
+
+        assert!(res.is_err());
+        assert_eq!(dropped.load(Ordering::SeqCst), 1);
+    })
+}
+
+#[test]
+fn spawn_after_completion() {
+    task::block_on(async {
+        let scope = task::scope(|s| async move { s }).await;
+
+        // A clone of the scope that escaped it can't spawn children that would never run.
+        let res = std::panic::catch_unwind(|| scope.spawn(async {}));
+        assert!(res.is_err());
+    })
+}
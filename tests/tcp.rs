@@ -20,6 +20,9 @@ fn connect() -> io::Result<()> {
         let t = task::spawn(async move { listener.accept().await });
 
         let stream2 = TcpStream::connect(&addr).await?;
+        #[cfg(feature = "unstable")]
+        let stream1 = t.await.unwrap()?.0;
+        #[cfg(not(feature = "unstable"))]
         let stream1 = t.await?.0;
 
         assert_eq!(stream1.peer_addr()?, stream2.local_addr()?);
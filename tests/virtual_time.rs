@@ -0,0 +1,82 @@
+#![cfg(all(feature = "unstable", not(target_os = "unknown")))]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_std::prelude::*;
+use async_std::rt::TestRuntime;
+use async_std::{future, io, stream, task};
+
+#[test]
+fn auto_advance() {
+    let runtime = TestRuntime::new();
+    let start = runtime.now();
+    let real_start = Instant::now();
+
+    runtime.block_on(async {
+        let res = future::timeout(Duration::from_secs(60), future::pending::<()>()).await;
+        assert!(res.is_err());
+
+        let res: io::Result<()> = io::timeout(Duration::from_secs(60), future::pending()).await;
+        assert_eq!(res.unwrap_err().kind(), io::ErrorKind::TimedOut);
+
+        let ticks = stream::interval(Duration::from_secs(60)).take(3).count().await;
+        assert_eq!(ticks, 3);
+
+        task::spawn(task::sleep(Duration::from_secs(60))).await.unwrap();
+    });
+
+    assert_eq!(runtime.now() - start, Duration::from_secs(6 * 60));
+    assert!(real_start.elapsed() < Duration::from_secs(60));
+}
+
+#[test]
+fn manual_advance() {
+    let runtime = TestRuntime::new();
+    runtime.set_auto_advance(false);
+
+    let done = Arc::new(AtomicBool::new(false));
+    let flag = done.clone();
+    let handle = runtime.spawn(async move {
+        task::sleep(Duration::from_secs(10)).await;
+        flag.store(true, Ordering::SeqCst);
+    });
+
+    runtime.block_on(async {
+        task::yield_now().await;
+
+        runtime.advance(Duration::from_secs(5));
+        task::yield_now().await;
+        assert!(!done.load(Ordering::SeqCst));
+
+        runtime.advance(Duration::from_secs(5));
+        handle.await.unwrap();
+        assert!(done.load(Ordering::SeqCst));
+    });
+}
+
+#[test]
+fn timers_fire_in_order() {
+    let runtime = TestRuntime::new();
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    runtime.block_on(async {
+        let handles: Vec<_> = [3, 1, 2]
+            .iter()
+            .map(|&secs| {
+                let order = order.clone();
+                task::spawn(async move {
+                    task::sleep(Duration::from_secs(secs)).await;
+                    order.lock().unwrap().push(secs);
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+    });
+
+    assert_eq!(*order.lock().unwrap(), vec![1, 2, 3]);
+}
@@ -0,0 +1,181 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::sync::{oneshot, watch, WatchRecvError};
+use async_std::task;
+
+#[cfg(not(target_os = "unknown"))]
+use async_std::task::spawn;
+#[cfg(target_os = "unknown")]
+use async_std::task::spawn_local as spawn;
+
+#[cfg(target_arch = "wasm32")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn smoke() {
+    task::block_on(async {
+        let (s, mut r) = watch(1);
+        assert_eq!(*r.borrow(), 1);
+        assert_eq!(*s.borrow(), 1);
+        assert!(!r.has_changed());
+
+        s.send(2);
+        assert!(r.has_changed());
+        r.changed().await.unwrap();
+        assert_eq!(*r.borrow(), 2);
+        assert!(!r.has_changed());
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn changed_waits() {
+    task::block_on(async {
+        let (s, mut r) = watch(0);
+
+        let mut changed = Box::pin(r.changed());
+        assert!(futures::poll!(changed.as_mut()).is_pending());
+
+        spawn(async move { s.send(1) });
+        changed.await.unwrap();
+        assert_eq!(*r.borrow(), 1);
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn coalesces_changes() {
+    task::block_on(async {
+        let (s, mut r) = watch(0);
+        s.send(1);
+        s.send(2);
+        s.send(3);
+
+        r.changed().await.unwrap();
+        assert_eq!(*r.borrow(), 3);
+        assert!(future::timeout(ms(50), r.changed()).await.is_err());
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn many_receivers() {
+    task::block_on(async {
+        let (s, r) = watch(0);
+
+        let mut handles = Vec::new();
+        let mut waiting = Vec::new();
+        for _ in 0..10 {
+            let mut r = r.clone();
+            let (ready, is_ready) = oneshot();
+            waiting.push(is_ready);
+            handles.push(spawn(async move {
+                let mut changed = Box::pin(r.changed());
+                assert!(futures::poll!(changed.as_mut()).is_pending());
+                ready.send(()).unwrap();
+                changed.await.unwrap();
+                *r.borrow()
+            }));
+        }
+        assert_eq!(s.receiver_count(), 11);
+
+        // Send once every receiver is waiting for a change.
+        for is_ready in waiting {
+            is_ready.await.unwrap();
+        }
+        s.send(5);
+        for handle in handles {
+            assert_eq!(handle.await.unwrap(), 5);
+        }
+
+        drop(r);
+        assert_eq!(s.receiver_count(), 0);
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn sender_dropped() {
+    task::block_on(async {
+        let (s, mut r) = watch(0);
+        s.send(1);
+        drop(s);
+
+        // The last change is still observed before the error.
+        r.changed().await.unwrap();
+        assert_eq!(*r.borrow(), 1);
+        assert_eq!(r.changed().await, Err(WatchRecvError));
+
+        let (s, mut r) = watch(0);
+        let mut changed = Box::pin(r.changed());
+        assert!(futures::poll!(changed.as_mut()).is_pending());
+        spawn(async move { drop(s) });
+        assert_eq!(changed.await, Err(WatchRecvError));
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn subscribe() {
+    task::block_on(async {
+        let (s, r) = watch(0);
+        drop(r);
+        assert_eq!(s.receiver_count(), 0);
+
+        // The value is kept without receivers.
+        s.send(1);
+        let mut r = s.subscribe();
+        assert_eq!(*r.borrow(), 1);
+        assert!(!r.has_changed());
+
+        s.send(2);
+        assert_eq!(*r.borrow_and_update(), 2);
+        assert!(!r.has_changed());
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn stream() {
+    task::block_on(async {
+        let (s, mut r) = watch(0);
+
+        s.send(1);
+        assert_eq!(r.next().await, Some(1));
+
+        for i in 2..=5 {
+            let mut next = r.next();
+            assert!(futures::poll!(&mut next).is_pending());
+            s.send(i);
+            assert_eq!(next.await, Some(i));
+        }
+
+        drop(s);
+        assert_eq!(r.next().await, None);
+    })
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn cancel_changed() {
+    task::block_on(async {
+        let (s, mut r) = watch(0);
+
+        for _ in 0..10 {
+            assert!(future::timeout(ms(1), r.changed()).await.is_err());
+        }
+
+        s.send(1);
+        r.changed().await.unwrap();
+        assert_eq!(*r.borrow(), 1);
+    })
+}